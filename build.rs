@@ -0,0 +1,51 @@
+//! With the `embed-inputs` feature, generates the table of the input files (the
+//! `.txt` files under `inputs/` and its subdirectories) that the library embeds.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+fn main() -> io::Result<()> {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return Ok(());
+    }
+    let inputs = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+    println!("cargo:rerun-if-changed={}", inputs.display());
+
+    let mut files = Vec::new();
+    collect(&inputs, "", &mut files)?;
+    files.sort();
+    let mut table = String::from("static FILES: &[(&str, &[u8])] = &[\n");
+    for (name, path) in files {
+        table += &format!("    ({name:?}, include_bytes!({:?})),\n", path.display());
+    }
+    table += "];\n";
+    let out = PathBuf::from(env::var_os("OUT_DIR").expect("set by cargo"));
+    fs::write(out.join("embedded_inputs.rs"), table)
+}
+
+/// Adds the input files in `dir` (named relative to the input directory, with `/`
+/// separators) to `files`, recursively.
+fn collect(dir: &Path, prefix: &str, files: &mut Vec<(String, PathBuf)>) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if path.is_dir() {
+            collect(&path, &format!("{prefix}{name}/"), files)?;
+        } else if name.ends_with(".txt") {
+            println!("cargo:rerun-if-changed={}", path.display());
+            files.push((format!("{prefix}{name}"), path));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,266 @@
+//! Terminal animations of the step-by-step simulations: an [`Animation`] renders its
+//! current state as a frame of text, and a [`Player`] redraws the frames in place
+//! with ANSI escape codes.
+
+use std::{
+    io::{self, BufRead, Write},
+    str::FromStr,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{day10::Day10, day14::Day14, day9::Day9, Params, Part, Solution};
+
+/// Simulation state that can be drawn and advanced one step at a time.
+pub trait Animation {
+    /// Draws the current state, as lines of text.
+    fn frame(&self) -> String;
+
+    /// Advances by one step. Returns false (leaving the state as it is) once the
+    /// simulation is over.
+    fn step(&mut self) -> bool;
+}
+
+/// A day whose simulation can be animated.
+pub trait Animated: Solution {
+    fn animation(input: &Self::Input, part: Part, params: &Params) -> Result<Box<dyn Animation>>;
+}
+
+type AnimationFn = fn(&str, Part, &Params) -> Result<Box<dyn Animation>>;
+
+fn animated<A: Animated>(input: &str, part: Part, params: &Params) -> Result<Box<dyn Animation>> {
+    A::animation(&A::parse(input)?, part, params)
+}
+
+static ANIMATIONS: [(u8, AnimationFn); 3] = [
+    (9, animated::<Day9>),
+    (10, animated::<Day10>),
+    (14, animated::<Day14>),
+];
+
+/// All days that can be animated, in ascending order.
+pub fn days() -> impl Iterator<Item = u8> {
+    ANIMATIONS.iter().map(|a| a.0)
+}
+
+/// The animation of one part of the given day's puzzle, for the given input.
+pub fn animation(day: u8, part: Part, input: &str, params: &Params) -> Result<Box<dyn Animation>> {
+    let (_, animation) = ANIMATIONS
+        .iter()
+        .find(|a| a.0 == day)
+        .with_context(|| format!("no animation for day {day}"))?;
+    animation(input, part, params)
+}
+
+/// Commands to a [`Player`], one per input line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Pause, or resume when paused.
+    Pause,
+    /// Pause and advance by a single step.
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl FromStr for Control {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" | "p" => Ok(Control::Pause),
+            "s" | "n" => Ok(Control::Step),
+            "+" => Ok(Control::Faster),
+            "-" => Ok(Control::Slower),
+            "q" => Ok(Control::Quit),
+            other => Err(anyhow!("unknown command: {other}")),
+        }
+    }
+}
+
+const HELP: &str = "[enter] pause/resume  [s] step  [+/-] speed  [q] quit";
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const HOME: &str = "\x1b[H";
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+/// Plays an [`Animation`] at a frame rate, optionally controlled by [`Control`]s.
+pub struct Player {
+    fps: f64,
+    paused: bool,
+    controls: Option<Receiver<Control>>,
+}
+
+impl Player {
+    pub fn new(fps: f64) -> Self {
+        Player {
+            fps: fps.max(f64::MIN_POSITIVE),
+            paused: false,
+            controls: None,
+        }
+    }
+
+    /// Start paused on the first frame (only useful with controls).
+    pub fn paused(mut self) -> Self {
+        self.paused = true;
+        self
+    }
+
+    pub fn controls(mut self, controls: Receiver<Control>) -> Self {
+        self.controls = Some(controls);
+        self
+    }
+
+    /// Read the controls from stdin, one command per line.
+    pub fn stdin_controls(self) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                // unknown commands are ignored
+                if let Ok(control) = line.parse() {
+                    if tx.send(control).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        self.controls(rx)
+    }
+
+    /// Play the animation to its end (or until told to quit). Returns the number
+    /// of frames shown.
+    pub fn play(
+        &mut self,
+        animation: &mut dyn Animation,
+        out: &mut impl Write,
+    ) -> io::Result<usize> {
+        write!(out, "{HIDE_CURSOR}{CLEAR_SCREEN}")?;
+        let mut frames = 0;
+        loop {
+            self.draw(&animation.frame(), out)?;
+            frames += 1;
+            if !self.wait() || !animation.step() {
+                break;
+            }
+        }
+        write!(out, "{SHOW_CURSOR}")?;
+        out.flush()?;
+        Ok(frames)
+    }
+
+    fn draw(&self, frame: &str, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "{HOME}")?;
+        for line in frame.lines() {
+            writeln!(out, "{line}{CLEAR_LINE}")?;
+        }
+        if self.controls.is_some() {
+            let state = if self.paused { "paused" } else { "playing" };
+            writeln!(out, "{state} at {} fps  {HELP}{CLEAR_LINE}", self.fps)?;
+        }
+        write!(out, "{CLEAR_BELOW}")?;
+        out.flush()
+    }
+
+    /// Waits for the time to show the next frame. Returns false to quit.
+    fn wait(&mut self) -> bool {
+        let deadline = Instant::now() + Duration::from_secs_f64(1.0 / self.fps);
+        loop {
+            let Some(controls) = &self.controls else {
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                return true;
+            };
+            let control = if self.paused {
+                controls.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                controls.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            };
+            match control {
+                Ok(Control::Pause) => self.paused = !self.paused,
+                Ok(Control::Step) => {
+                    self.paused = true;
+                    return true;
+                }
+                Ok(Control::Faster) => self.fps *= 2.0,
+                Ok(Control::Slower) => self.fps /= 2.0,
+                Ok(Control::Quit) => return false,
+                Err(RecvTimeoutError::Timeout) => return true,
+                Err(RecvTimeoutError::Disconnected) => {
+                    // without controls, nobody could resume
+                    self.controls = None;
+                    self.paused = false;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Countdown(u32);
+
+    impl Animation for Countdown {
+        fn frame(&self) -> String {
+            format!("{} left\n", self.0)
+        }
+
+        fn step(&mut self) -> bool {
+            self.0 = match self.0.checked_sub(1) {
+                Some(left) => left,
+                None => return false,
+            };
+            true
+        }
+    }
+
+    #[test]
+    fn play_to_end() {
+        let mut out = Vec::new();
+        let frames = Player::new(1000.0).play(&mut Countdown(3), &mut out);
+        assert_eq!(frames.unwrap(), 4);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with(HIDE_CURSOR));
+        assert!(out.ends_with(SHOW_CURSOR));
+        assert!(out.contains(&format!("{HOME}3 left{CLEAR_LINE}\n")));
+        assert!(out.contains(&format!("{HOME}0 left{CLEAR_LINE}\n")));
+    }
+
+    #[test]
+    fn pause_and_step() {
+        let (tx, rx) = mpsc::channel();
+        for control in ["s", "s", "q", "s"] {
+            tx.send(control.parse().unwrap()).unwrap();
+        }
+        let mut player = Player::new(1.0).paused().controls(rx);
+        let mut out = Vec::new();
+        assert_eq!(player.play(&mut Countdown(10), &mut out).unwrap(), 3);
+        assert!(String::from_utf8(out).unwrap().contains("paused at 1 fps"));
+    }
+
+    #[test]
+    fn animate_examples() {
+        for day in days() {
+            let input = crate::example(day).unwrap();
+            let params = Params::load(day, "example").unwrap();
+            for part in Part::BOTH {
+                let mut animation = animation(day, part, &input, &params).unwrap();
+                let first = animation.frame();
+                let mut steps = 0;
+                while animation.step() {
+                    steps += 1;
+                }
+                assert!(steps > 0, "day {day} part {part}");
+                assert_ne!(animation.frame(), first, "day {day} part {part}");
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! The registry of known-correct answers, checked in as `answers.toml`.
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::Part;
+
+const ANSWERS_FILE: &str = "answers.toml";
+
+/// The expected answer for one part of one day's input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u8,
+    /// The input name, see [`crate::named`].
+    pub input: String,
+    pub part: Part,
+    pub answer: String,
+}
+
+impl Expected {
+    /// Solve the entry's puzzle (with the parameters of its input) and compare the
+    /// result to the expected answer.
+    pub fn check(&self) -> Result<()> {
+        let input = crate::named(self.day, &self.input)?;
+        let params = crate::Params::load(self.day, &self.input)?;
+        let answer = crate::solve(self.day, self.part, &input, &params)?;
+        if answer != self.answer {
+            bail!("expected {}, got {answer}", self.answer);
+        }
+        Ok(())
+    }
+
+    /// A unique name for the entry, e.g. `day9::example_big::part2`.
+    pub fn name(&self) -> String {
+        format!("day{}::{}::part{}", self.day, self.input, self.part)
+    }
+}
+
+/// Answers may be written as TOML integers or strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Integer(i64),
+    Text(String),
+}
+
+impl Answer {
+    fn into_string(self) -> String {
+        match self {
+            Answer::Integer(i) => i.to_string(),
+            Answer::Text(s) => s,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PartAnswers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// All expected answers, sorted by day, input name and part.
+#[derive(Debug, Default)]
+pub struct Answers(Vec<Expected>);
+
+impl Answers {
+    /// The path of the answers file in the crate root.
+    pub fn path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(ANSWERS_FILE)
+    }
+
+    /// Load the checked-in answers file (with the `embed-inputs` feature, the copy
+    /// embedded at build time if it is not found).
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        #[cfg(feature = "embed-inputs")]
+        if !path.exists() {
+            return Self::parse(include_str!("../answers.toml"));
+        }
+        let text =
+            fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("invalid answers file {}", path.display()))
+    }
+
+    /// Parse an answers file: tables `[dayN.<input name>]` with `part1` and `part2` keys.
+    pub fn parse(text: &str) -> Result<Self> {
+        let days: BTreeMap<String, BTreeMap<String, PartAnswers>> = toml::from_str(text)?;
+
+        let mut entries = Vec::new();
+        for (key, inputs) in days {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .with_context(|| format!("expected a table named dayN, found {key}"))?;
+            for (input, parts) in inputs {
+                for (part, answer) in [(Part::One, parts.part1), (Part::Two, parts.part2)] {
+                    if let Some(answer) = answer {
+                        entries.push(Expected {
+                            day,
+                            input: input.clone(),
+                            part,
+                            answer: answer.into_string(),
+                        });
+                    }
+                }
+            }
+        }
+        entries.sort_by(|l, r| (l.day, &l.input, l.part).cmp(&(r.day, &r.input, r.part)));
+        Ok(Answers(entries))
+    }
+
+    /// The expected answer, if it is known.
+    pub fn get(&self, day: u8, input: &str, part: Part) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|e| e.day == day && e.input == input && e.part == part)
+            .map(|e| e.answer.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Expected> {
+        self.0.iter()
+    }
+
+    /// All entries of the given day.
+    pub fn day(&self, day: u8) -> impl Iterator<Item = &Expected> {
+        self.0.iter().filter(move |e| e.day == day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse(
+            "[day10.example]\npart2 = '''\n#.\n.#'''\n\n[day2.real]\npart1 = 15\npart2 = \"12\"\n",
+        )
+        .unwrap();
+        assert_eq!(answers.get(2, "real", Part::One), Some("15"));
+        assert_eq!(answers.get(2, "real", Part::Two), Some("12"));
+        assert_eq!(answers.get(10, "example", Part::One), None);
+        assert_eq!(answers.get(10, "example", Part::Two), Some("#.\n.#"));
+        // sorted numerically by day
+        assert_eq!(answers.iter().next().unwrap().day, 2);
+    }
+
+    #[test]
+    fn reject_invalid_tables() {
+        assert!(Answers::parse("[dayX.real]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[day1.real]\npart3 = 1\n").is_err());
+    }
+
+    #[test]
+    fn checked_in_file_is_valid() {
+        let answers = Answers::load().unwrap();
+        assert!(answers.day(1).count() > 0);
+    }
+}
@@ -0,0 +1,307 @@
+//! Timing of the parse, part 1 and part 2 stages of the solvers, with JSON reports
+//! that can be compared against a stored baseline.
+
+use std::{fmt, hint::black_box, str::FromStr, time::Duration, time::Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use json::{object, JsonValue};
+
+use crate::{Params, Part, Solver};
+
+/// Differences below this are measurement noise and never count as a regression.
+const NOISE_FLOOR: Duration = Duration::from_micros(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Phase {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Phase::ALL
+            .into_iter()
+            .find(|p| p.as_str() == s)
+            .ok_or_else(|| anyhow!("unknown phase: {s}"))
+    }
+}
+
+/// Summary of the durations measured for one phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+        samples.sort_unstable();
+        let total: Duration = samples.iter().sum();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: total / samples.len() as u32,
+        }
+    }
+
+    fn to_json(self) -> JsonValue {
+        object! {
+            min_ns: self.min.as_nanos() as u64,
+            median_ns: self.median.as_nanos() as u64,
+            mean_ns: self.mean.as_nanos() as u64,
+        }
+    }
+
+    fn from_json(js: &JsonValue) -> Result<Self> {
+        let field = |name: &str| {
+            js[name]
+                .as_u64()
+                .map(Duration::from_nanos)
+                .with_context(|| format!("missing {name}"))
+        };
+        Ok(Stats {
+            min: field("min_ns")?,
+            median: field("median_ns")?,
+            mean: field("mean_ns")?,
+        })
+    }
+}
+
+/// The timings of all phases of one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayTimings {
+    pub day: u8,
+    /// Indexed in the order of [`Phase::ALL`].
+    pub phases: [Stats; 3],
+}
+
+impl DayTimings {
+    pub fn phase(&self, phase: Phase) -> Stats {
+        self.phases[phase as usize]
+    }
+}
+
+/// Time each phase of the solver separately, `iterations` times each.
+pub fn bench(
+    solver: &dyn Solver,
+    input: &str,
+    params: &Params,
+    iterations: usize,
+) -> Result<DayTimings> {
+    if iterations == 0 {
+        bail!("need at least one iteration");
+    }
+    let mut samples = [(); 3].map(|_| Vec::with_capacity(iterations));
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = black_box(solver.parse_input(input)?);
+        samples[Phase::Parse as usize].push(start.elapsed());
+
+        for (part, phase) in Part::BOTH.into_iter().zip([Phase::Part1, Phase::Part2]) {
+            let start = Instant::now();
+            black_box(solver.solve_part(parsed.as_ref(), part, params)?);
+            samples[phase as usize].push(start.elapsed());
+        }
+    }
+
+    Ok(DayTimings {
+        day: solver.day(),
+        phases: samples.map(|mut s| Stats::from_samples(&mut s)),
+    })
+}
+
+/// A phase that got slower than allowed compared to the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub phase: Phase,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    /// Relative slowdown, e.g. 0.25 for 25% slower.
+    pub fn slowdown(&self) -> f64 {
+        self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0
+    }
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} {}: {:.2?} -> {:.2?} (+{:.0}%)",
+            self.day,
+            self.phase,
+            self.baseline,
+            self.current,
+            self.slowdown() * 100.0
+        )
+    }
+}
+
+/// A benchmark run over several days.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// The input name, see [`crate::named`].
+    pub input: String,
+    pub iterations: usize,
+    pub days: Vec<DayTimings>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        let days: Vec<JsonValue> = self
+            .days
+            .iter()
+            .map(|d| {
+                let mut phases = JsonValue::new_object();
+                for phase in Phase::ALL {
+                    phases[phase.as_str()] = d.phase(phase).to_json();
+                }
+                object! { day: d.day, phases: phases }
+            })
+            .collect();
+        let report = object! {
+            input: self.input.as_str(),
+            iterations: self.iterations,
+            days: days,
+        };
+        json::stringify_pretty(report, 2)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        let js = json::parse(text)?;
+        let mut days = Vec::new();
+        for d in js["days"].members() {
+            let day = d["day"].as_u8().context("missing day")?;
+            let mut phases = Vec::new();
+            for phase in Phase::ALL {
+                let stats = Stats::from_json(&d["phases"][phase.as_str()])
+                    .with_context(|| format!("invalid {phase} timings for day {day}"))?;
+                phases.push(stats);
+            }
+            days.push(DayTimings {
+                day,
+                phases: phases.try_into().expect("one entry per phase"),
+            });
+        }
+        Ok(Report {
+            input: js["input"].as_str().context("missing input")?.to_owned(),
+            iterations: js["iterations"].as_usize().context("missing iterations")?,
+            days,
+        })
+    }
+
+    /// All phases whose median got slower than the baseline's by more than
+    /// `threshold` (e.g. 0.1 for 10%). Days missing from the baseline are skipped.
+    pub fn compare(&self, baseline: &Report, threshold: f64) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+        for cur in &self.days {
+            let Some(base) = baseline.days.iter().find(|b| b.day == cur.day) else {
+                continue;
+            };
+            for phase in Phase::ALL {
+                let baseline = base.phase(phase).median;
+                let current = cur.phase(phase).median;
+                if current.saturating_sub(baseline) < NOISE_FLOOR {
+                    continue;
+                }
+                if current.as_secs_f64() > baseline.as_secs_f64() * (1.0 + threshold) {
+                    regressions.push(Regression {
+                        day: cur.day,
+                        phase,
+                        baseline,
+                        current,
+                    });
+                }
+            }
+        }
+        regressions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings(day: u8, median_ms: [u64; 3]) -> DayTimings {
+        DayTimings {
+            day,
+            phases: median_ms.map(|ms| {
+                let d = Duration::from_millis(ms);
+                Stats {
+                    min: d,
+                    median: d,
+                    mean: d,
+                }
+            }),
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let mut samples = [5, 1, 4, 2, 3].map(Duration::from_millis);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let report = Report {
+            input: "real".to_owned(),
+            iterations: 3,
+            days: vec![timings(1, [1, 2, 3]), timings(16, [4, 500, 6000])],
+        };
+        assert_eq!(Report::from_json(&report.to_json()).unwrap(), report);
+    }
+
+    #[test]
+    fn flag_regressions() {
+        let baseline = Report {
+            input: "real".to_owned(),
+            iterations: 1,
+            days: vec![timings(1, [10, 10, 10]), timings(2, [10, 10, 10])],
+        };
+        let current = Report {
+            days: vec![
+                timings(1, [10, 11, 20]),
+                timings(2, [5, 10, 10]),
+                timings(3, [100, 100, 100]),
+            ],
+            ..baseline.clone()
+        };
+
+        let regressions = current.compare(&baseline, 0.2);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].day, 1);
+        assert_eq!(regressions[0].phase, Phase::Part2);
+        assert!((regressions[0].slowdown() - 1.0).abs() < 1e-9);
+    }
+}
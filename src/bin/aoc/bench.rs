@@ -0,0 +1,69 @@
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+use aoc_2022::bench::{self, Phase, Report};
+
+use crate::{DaySelection, InputArg, ParamArgs};
+
+pub fn bench(
+    selection: DaySelection,
+    input: &InputArg,
+    params: &ParamArgs,
+    iterations: usize,
+    output: Option<&Path>,
+    baseline: Option<&Path>,
+    threshold: f64,
+) -> Result<()> {
+    let mut report = Report {
+        input: input.to_string(),
+        iterations,
+        days: Vec::new(),
+    };
+
+    println!("day  {:>12} {:>12} {:>12}", "parse", "part 1", "part 2");
+    for day in selection.days() {
+        let solver = aoc_2022::solver(day).context("no solver for this day")?;
+        let text = input.load(day)?;
+        let timings = bench::bench(solver, &text, &params.get(day, input), iterations)
+            .with_context(|| format!("day {day} failed"))?;
+
+        let medians: Vec<String> = Phase::ALL
+            .iter()
+            .map(|&phase| format!("{:>12.2?}", timings.phase(phase).median))
+            .collect();
+        println!("{day:>3}  {}", medians.join(" "));
+        report.days.push(timings);
+    }
+
+    if let Some(output) = output {
+        fs::write(output, report.to_json())
+            .with_context(|| format!("cannot write {}", output.display()))?;
+    }
+
+    if let Some(baseline) = baseline {
+        let text = fs::read_to_string(baseline)
+            .with_context(|| format!("cannot read {}", baseline.display()))?;
+        let baseline = Report::from_json(&text)
+            .with_context(|| format!("invalid baseline {}", baseline.display()))?;
+        if baseline.input != report.input {
+            bail!(
+                "the baseline was measured on the {} input, not on {}",
+                baseline.input,
+                report.input
+            );
+        }
+
+        let regressions = report.compare(&baseline, threshold / 100.0);
+        for regression in &regressions {
+            println!("REGRESSION {regression}");
+        }
+        if !regressions.is_empty() {
+            bail!(
+                "{} phase(s) slower than the baseline by more than {threshold}%",
+                regressions.len()
+            );
+        }
+        println!("no regressions against the baseline");
+    }
+    Ok(())
+}
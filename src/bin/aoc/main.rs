@@ -0,0 +1,691 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use aoc_2022::{
+    animate::{self, Player},
+    answers::Answers,
+    cancel::{self, Cancel},
+    client::{self, Client, Fetched},
+    debugger, differential, generate, image, logging,
+    params::{Config, Override},
+    runner::{self, Status},
+    scaffold::Project,
+    serve::Server,
+    submit::{self, Guesses, Outcome},
+    Params, Part,
+};
+use clap::{Parser, Subcommand};
+use log::info;
+
+mod bench;
+
+/// Advent of Code 2022 solutions
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Show more diagnostics on stderr (-v for debug, -vv for trace output);
+    /// the default level can also be set with $AOC_LOG
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Only show errors on stderr
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Set a puzzle parameter, overriding aoc.toml: KEY=VALUE for every day that has
+    /// the parameter, or dayN.KEY=VALUE for one day
+    #[arg(long = "param", global = true, value_name = "KEY=VALUE")]
+    params: Vec<Override>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the puzzle of one day, or of all days
+    Run {
+        /// Day to solve, or "all"
+        day: DaySelection,
+        /// Only solve this part (default: both parts)
+        #[arg(short, long)]
+        part: Option<Part>,
+        /// Puzzle input: "example", "real", the name of another input of the day, the
+        /// path to an input file, or "-" for stdin
+        #[arg(short, long, default_value = "real")]
+        input: InputArg,
+        /// Solve every input of the day: inputs/dayN.txt, inputs/dayN_<name>.txt and
+        /// inputs/dayN/<name>.txt
+        #[arg(short, long, conflicts_with = "input")]
+        all_inputs: bool,
+        /// Also draw the solved state of days 8, 12 or 14 to this .png, .ppm or .svg file
+        #[arg(long, requires = "part", conflicts_with_all = ["jobs", "markdown", "all_inputs"])]
+        image: Option<PathBuf>,
+        /// Solve the parts on this many threads and print a summary table
+        #[arg(short, long)]
+        jobs: Option<usize>,
+        /// Print the summary table as Markdown
+        #[arg(long)]
+        markdown: bool,
+        /// Give up on a part after this many seconds (only days 11, 14, 15 and 16 can
+        /// be stopped)
+        #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Time the parse, part 1 and part 2 stages of the solvers
+    Bench {
+        /// Day to benchmark, or "all"
+        #[arg(default_value = "all")]
+        day: DaySelection,
+        /// Puzzle input: "example", "real", the name of another input of the day, or
+        /// the path to an input file
+        #[arg(short, long, default_value = "real")]
+        input: InputArg,
+        /// Number of times each stage is run
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+        /// Write the report as JSON to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Compare against a report written earlier with --output
+        #[arg(short, long)]
+        baseline: Option<PathBuf>,
+        /// Slowdown against the baseline (in percent) that counts as a regression
+        #[arg(short, long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Check the solvers against the known answers in answers.toml
+    Verify {
+        /// Day to check, or "all"
+        #[arg(default_value = "all")]
+        day: DaySelection,
+    },
+    /// Download the puzzle input of a day (unless it was downloaded before)
+    ///
+    /// The session token is read from $AOC_SESSION, or else from the file named by
+    /// $AOC_SESSION_FILE (default: ~/.adventofcode.session). Set $AOC_BASE_URL to
+    /// use another server.
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Submit the answer to one part of a day's puzzle
+    ///
+    /// Guesses are logged in guesses.toml next to the inputs: answers known to be
+    /// wrong are not sent again, and the waiting time after a wrong answer is kept.
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        part: Part,
+        /// Submit this answer instead of solving the real input
+        #[arg(short, long)]
+        answer: Option<String>,
+    },
+    /// Print a random input for a day, generated from a seed
+    ///
+    /// The size counts the records of the input: elves, rounds, rucksacks, move
+    /// orders, characters, directories, motions, instructions, monkeys, packet pairs,
+    /// rock paths or valves. The forest of day 8 is size × size trees, the heightmap
+    /// of day 12 has size rows, and for day 15 the size is the upper bound of the
+    /// search area (20 or 4000000).
+    Gen {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(short, long, default_value_t = 0)]
+        seed: u64,
+        /// Size of the input (default: about the size of the real input)
+        #[arg(short = 'n', long)]
+        size: Option<usize>,
+    },
+    /// Watch the simulation of a day in the terminal (days 9, 10 and 14)
+    ///
+    /// Control the animation by typing a command and pressing enter: nothing to
+    /// pause or resume, "s" to pause and step, "+" or "-" to change the speed, and
+    /// "q" to quit.
+    Animate {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(short, long, default_value_t = Part::One)]
+        part: Part,
+        /// Puzzle input: "example", "real", the name of another input of the day, or
+        /// the path to an input file
+        #[arg(short, long, default_value = "example")]
+        input: InputArg,
+        /// Frames per second
+        #[arg(short, long, default_value_t = 10.0)]
+        fps: f64,
+        /// Start paused on the first frame
+        #[arg(long)]
+        paused: bool,
+    },
+    /// Step through the simulation of a day interactively (days 5, 10, 11 and 14)
+    ///
+    /// Commands: enter or "s [n]" to step forward, "b [n]" to go back, "g <n>" to go
+    /// to a step, "u <value> <op> <number>" to step until a condition holds, "r" to
+    /// run to the end, "p" to show the state, "v" to list the values, "q" to quit.
+    Debug {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(short, long, default_value_t = Part::One)]
+        part: Part,
+        /// Puzzle input: "example", "real", the name of another input of the day, or
+        /// the path to an input file
+        #[arg(short, long, default_value = "example")]
+        input: InputArg,
+    },
+    /// Compare the solvers with slow reference solutions on generated inputs
+    ///
+    /// Prints the smallest input found on which they disagree.
+    Diff {
+        /// Day to check (default: all days with a reference solution)
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Number of seeds to generate inputs from
+        #[arg(short, long, default_value_t = 20)]
+        seeds: u64,
+    },
+    /// Add a new day: the solver skeleton with tests for the example, the binary,
+    /// empty input files and an answers entry
+    ///
+    /// Refuses to overwrite a day that exists already.
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Solve puzzles for other tools over HTTP, on localhost only
+    ///
+    /// POST the puzzle input to /day/{n}/part/{p} (with ?input=<name> to use the
+    /// parameters of another input than the real one) to get the answer, the time
+    /// taken or the error as JSON.
+    Serve {
+        #[arg(short, long, default_value_t = 2022)]
+        port: u16,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum DaySelection {
+    All,
+    One(u8),
+}
+
+impl DaySelection {
+    fn days(self) -> Vec<u8> {
+        match self {
+            DaySelection::All => aoc_2022::days().collect(),
+            DaySelection::One(day) => vec![day],
+        }
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(DaySelection::All);
+        }
+        let day = s
+            .parse()
+            .map_err(|_| anyhow!("expected a day number or \"all\""))?;
+        ensure!(
+            aoc_2022::days().any(|d| d == day),
+            "no solver for day {day}"
+        );
+        Ok(DaySelection::One(day))
+    }
+}
+
+#[derive(Clone)]
+enum InputArg {
+    /// "example", "real", or another input of the day, see [`aoc_2022::named`].
+    Named(String),
+    Path(PathBuf),
+}
+
+impl InputArg {
+    /// The same input file cannot be meant for several days.
+    fn check_selection(&self, selection: DaySelection) -> Result<()> {
+        if let (DaySelection::All, InputArg::Path(_)) = (selection, self) {
+            bail!("an input file can only be given for a single day");
+        }
+        Ok(())
+    }
+
+    /// The inputs of a day to solve: this one, or with `all` every input of the day.
+    fn of_day(&self, day: u8, all: bool) -> Result<Vec<InputArg>> {
+        if !all {
+            return Ok(vec![self.clone()]);
+        }
+        let names = aoc_2022::input_names(day)?;
+        ensure!(!names.is_empty(), "no inputs for day {day}");
+        Ok(names.into_iter().map(InputArg::Named).collect())
+    }
+
+    /// The name of the input in `aoc.toml` and `answers.toml`: input files are solved
+    /// with the parameters of the real input, and have no known answers.
+    fn name(&self) -> Option<&str> {
+        match self {
+            InputArg::Named(name) => Some(name),
+            InputArg::Path(_) => None,
+        }
+    }
+
+    fn load(&self, day: u8) -> Result<String> {
+        match self {
+            InputArg::Named(name) => Ok(aoc_2022::named(day, name)?),
+            InputArg::Path(path) => Ok(aoc_2022::load(path)?),
+        }
+    }
+}
+
+impl FromStr for InputArg {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // names are plain words, anything else is a path
+        Ok(match s.contains(['.', '/', '\\']) || s == "-" {
+            true => InputArg::Path(s.into()),
+            false => InputArg::Named(s.to_owned()),
+        })
+    }
+}
+
+impl fmt::Display for InputArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputArg::Named(name) => f.write_str(name),
+            InputArg::Path(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The puzzle parameters from `aoc.toml`, with the `--param` overrides applied.
+struct ParamArgs {
+    config: Config,
+    overrides: Vec<Override>,
+}
+
+impl ParamArgs {
+    /// Fails if an override is meant for none of the selected days.
+    fn new(overrides: &[Override], days: &[u8]) -> Result<Self> {
+        let config = Config::load()?;
+        for o in overrides {
+            let used = match o.day {
+                Some(day) => days.contains(&day),
+                None => days
+                    .iter()
+                    .any(|&day| config.params(day, "real").has(&o.key)),
+            };
+            ensure!(used, "--param {o}: no selected day has this parameter");
+        }
+        Ok(ParamArgs {
+            config,
+            overrides: overrides.to_vec(),
+        })
+    }
+
+    fn get(&self, day: u8, input: &InputArg) -> Params {
+        let mut params = self.config.params(day, input.name().unwrap_or("real"));
+        params.apply(&self.overrides);
+        params
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    logging::init(cli.verbose, cli.quiet)?;
+    let overrides = &cli.params;
+    if let Command::Verify { .. }
+    | Command::Fetch { .. }
+    | Command::Gen { .. }
+    | Command::Diff { .. }
+    | Command::New { .. }
+    | Command::Serve { .. } = cli.command
+    {
+        ensure!(
+            overrides.is_empty(),
+            "--param only applies to run, bench, submit, animate and debug"
+        );
+    }
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            all_inputs,
+            image,
+            jobs,
+            markdown,
+            timeout,
+        } => {
+            input.check_selection(day)?;
+            let params = ParamArgs::new(overrides, &day.days())?;
+            match (jobs, markdown) {
+                (None, false) => run(
+                    day,
+                    part,
+                    &input,
+                    all_inputs,
+                    &params,
+                    timeout,
+                    image.as_deref(),
+                ),
+                _ => run_parallel(
+                    day, part, &input, all_inputs, &params, timeout, jobs, markdown,
+                ),
+            }
+        }
+        Command::Bench {
+            day,
+            input,
+            iterations,
+            output,
+            baseline,
+            threshold,
+        } => {
+            input.check_selection(day)?;
+            bench::bench(
+                day,
+                &input,
+                &ParamArgs::new(overrides, &day.days())?,
+                iterations,
+                output.as_deref(),
+                baseline.as_deref(),
+                threshold,
+            )
+        }
+        Command::Verify { day } => verify(day),
+        Command::Fetch { day } => fetch(day),
+        Command::Submit { day, part, answer } => submit(day, part, answer, overrides),
+        Command::Gen { day, seed, size } => gen(day, seed, size),
+        Command::Animate {
+            day,
+            part,
+            input,
+            fps,
+            paused,
+        } => animate(day, part, &input, overrides, fps, paused),
+        Command::Debug { day, part, input } => debug(day, part, &input, overrides),
+        Command::Diff { day, seeds } => diff(day, seeds),
+        Command::New { day } => new(day),
+        Command::Serve { port } => serve(port),
+    }
+}
+
+fn run(
+    selection: DaySelection,
+    part: Option<Part>,
+    input: &InputArg,
+    all_inputs: bool,
+    params: &ParamArgs,
+    timeout: Option<Duration>,
+    image: Option<&Path>,
+) -> Result<()> {
+    if let Some(path) = image {
+        match selection {
+            DaySelection::One(day) if image::days().any(|d| d == day) => {}
+            _ => bail!("images can only be drawn for one of days 8, 12 and 14"),
+        }
+        image::Format::of(path)?;
+    }
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+
+    for day in selection.days() {
+        let solver = aoc_2022::solver(day).context("no solver for this day")?;
+        for input in input.of_day(day, all_inputs)? {
+            let text = input.load(day)?;
+            let parsed = solver.parse_input(&text)?;
+            let params = params.get(day, &input);
+            for &part in &parts {
+                let params = params.clone().with_cancel(Cancel::new(timeout));
+                let answer = match (solver.solve_part(parsed.as_ref(), part, &params), timeout) {
+                    (Ok(answer), _) => answer,
+                    (Err(e), Some(timeout)) if cancel::is_cancelled(&e) => {
+                        bail!("day {day} part {part} ({input}) timed out after {timeout:?}")
+                    }
+                    (Err(e), _) => {
+                        return Err(e.context(format!("day {day} part {part} ({input}) failed")))
+                    }
+                };
+                match all_inputs {
+                    true => print_answer(&format!("day {day} part {part} ({input})"), &answer),
+                    false => print_answer(&format!("day {day} part {part}"), &answer),
+                }
+                if let Some(path) = image {
+                    image::image(day, part, &text, &params)?.save(path)?;
+                    info!("day {day} part {part}: image saved to {}", path.display());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Solve the parts of all selected days at once, and print a summary table.
+#[allow(clippy::too_many_arguments)]
+fn run_parallel(
+    selection: DaySelection,
+    part: Option<Part>,
+    input: &InputArg,
+    all_inputs: bool,
+    params: &ParamArgs,
+    timeout: Option<Duration>,
+    jobs: Option<usize>,
+    markdown: bool,
+) -> Result<()> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    let mut tasks = Vec::new();
+    // loaded once per input, as stdin can only be read once
+    let mut texts = BTreeMap::new();
+    for day in selection.days() {
+        for input in input.of_day(day, all_inputs)? {
+            texts.insert((day, input.to_string()), input.load(day));
+            for &part in &parts {
+                tasks.push((day, input.to_string(), part));
+            }
+        }
+    }
+    let jobs = match jobs {
+        Some(jobs) => jobs,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+
+    let mut runs = runner::run_all(&tasks, jobs, timeout, |day, name, part, cancel| {
+        let input = match all_inputs {
+            true => &InputArg::Named(name.to_owned()),
+            false => input,
+        };
+        let text = match &texts[&(day, name.to_owned())] {
+            Ok(text) => text,
+            Err(e) => bail!("{e:#}"),
+        };
+        let params = params.get(day, input).with_cancel(cancel.clone());
+        aoc_2022::solve(day, part, text, &params)
+    });
+    // the known answers are for the parameters in aoc.toml
+    if input.name().is_some() && params.overrides.is_empty() {
+        runner::check(&mut runs, &Answers::load()?);
+    }
+    match markdown {
+        true => print!("{}", runner::markdown(&runs)),
+        false => print!("{}", runner::table(&runs)),
+    }
+
+    let failed = runs
+        .iter()
+        .filter(|r| {
+            matches!(
+                r.status(),
+                Status::Failed | Status::TimedOut | Status::Wrong
+            )
+        })
+        .count();
+    ensure!(failed == 0, "{failed} of {} parts failed", runs.len());
+    Ok(())
+}
+
+fn print_answer(label: &str, answer: &str) {
+    if answer.contains('\n') {
+        // multi-line answers (e.g. the day 10 CRT) start on their own line
+        println!("{label}:\n{answer}");
+    } else {
+        println!("{label}: {answer}");
+    }
+}
+
+/// A positive number of seconds, such as `10` or `0.5`.
+fn parse_seconds(s: &str) -> Result<Duration> {
+    let seconds: f64 = s
+        .parse()
+        .with_context(|| format!("expected seconds, found {s}"))?;
+    ensure!(seconds > 0.0, "the timeout must be positive");
+    Duration::try_from_secs_f64(seconds).with_context(|| format!("invalid timeout {s}"))
+}
+
+fn verify(selection: DaySelection) -> Result<()> {
+    let answers = Answers::load()?;
+    let mut checked = 0;
+    let mut failed = 0;
+
+    for day in selection.days() {
+        for entry in answers.day(day) {
+            checked += 1;
+            match entry.check() {
+                Ok(()) => println!("ok     {}", entry.name()),
+                Err(e) => {
+                    failed += 1;
+                    println!("FAILED {}: {e:#}", entry.name());
+                }
+            }
+        }
+    }
+
+    ensure!(failed == 0, "{failed} of {checked} answers are wrong");
+    println!("all {checked} answers are correct");
+    Ok(())
+}
+
+fn fetch(day: u8) -> Result<()> {
+    match client::fetch_input(day, &aoc_2022::input_path(day), Client::from_env)? {
+        Fetched::Cached(path) => println!("day {day}: already cached at {}", path.display()),
+        Fetched::Downloaded(path) => println!("day {day}: saved to {}", path.display()),
+    }
+    Ok(())
+}
+
+fn submit(day: u8, part: Part, answer: Option<String>, overrides: &[Override]) -> Result<()> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let input = InputArg::Named("real".to_owned());
+            let params = ParamArgs::new(overrides, &[day])?.get(day, &input);
+            aoc_2022::solve(day, part, &input.load(day)?, &params)?
+        }
+    };
+    ensure!(
+        !answer.contains('\n'),
+        "multi-line answers must be read and passed with --answer:\n{answer}"
+    );
+
+    let client = Client::from_env()?;
+    let path = Guesses::path();
+    let mut guesses = Guesses::load(&path)?;
+    let sleep = |wait| {
+        info!("waiting {wait:?} before answering");
+        thread::sleep(wait);
+    };
+    let outcome = submit::submit(&client, &mut guesses, day, part, &answer, sleep);
+    // the log is updated even if the submission failed after a wait
+    guesses.save(&path)?;
+
+    match outcome? {
+        Outcome::Correct => println!("day {day} part {part}: {answer} is correct"),
+        wrong => bail!("day {day} part {part}: {answer} is wrong ({wrong:?})"),
+    }
+    Ok(())
+}
+
+fn gen(day: u8, seed: u64, size: Option<usize>) -> Result<()> {
+    let size = match size {
+        Some(size) => size,
+        None => {
+            generate::real_size(day).with_context(|| format!("no input generator for day {day}"))?
+        }
+    };
+    print!("{}", generate::generate(day, seed, size)?);
+    Ok(())
+}
+
+fn animate(
+    day: u8,
+    part: Part,
+    input: &InputArg,
+    overrides: &[Override],
+    fps: f64,
+    paused: bool,
+) -> Result<()> {
+    let params = ParamArgs::new(overrides, &[day])?.get(day, input);
+    let mut animation = animate::animation(day, part, &input.load(day)?, &params)?;
+    let mut player = Player::new(fps).stdin_controls();
+    if paused {
+        player = player.paused();
+    }
+    player.play(animation.as_mut(), &mut std::io::stdout().lock())?;
+    Ok(())
+}
+
+fn debug(day: u8, part: Part, input: &InputArg, overrides: &[Override]) -> Result<()> {
+    let params = ParamArgs::new(overrides, &[day])?.get(day, input);
+    let mut debugger = debugger::debugger(day, part, &input.load(day)?, &params)?;
+    debugger.repl(std::io::stdin().lock(), &mut std::io::stdout().lock())
+}
+
+fn diff(day: Option<u8>, seeds: u64) -> Result<()> {
+    let days = match day {
+        Some(day) => vec![day],
+        None => differential::days().collect(),
+    };
+    let mut failed = 0;
+    for day in days {
+        match differential::check(day, 0..seeds)? {
+            None => println!("ok     day {day}: {seeds} seeds"),
+            Some(disagreement) => {
+                failed += 1;
+                println!("FAILED {disagreement}");
+            }
+        }
+    }
+    ensure!(
+        failed == 0,
+        "{failed} solvers disagree with their reference"
+    );
+    Ok(())
+}
+
+fn new(day: u8) -> Result<()> {
+    for path in Project::current().new_day(day)? {
+        println!("{}", path.display());
+    }
+    println!("day {day} added: fill in the example input and its answers, then solve!");
+    Ok(())
+}
+
+fn serve(port: u16) -> Result<()> {
+    let server = Server::bind(port)?;
+    println!(
+        "solving on http://localhost:{}/day/{{n}}/part/{{p}}",
+        server.port()
+    );
+    server.run();
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Cooperative cancellation of long-running solvers: their loops poll a [`Cancel`]
+//! token (handed to them with the [`crate::Params`]) and give up with [`Cancelled`]
+//! once it is cancelled or its time budget is used up.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use thiserror::Error;
+
+/// The error of a cancelled solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("timed out")]
+pub struct Cancelled;
+
+/// Whether an error (or one of its causes) is a cancellation.
+pub fn is_cancelled(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| cause.is::<Cancelled>())
+}
+
+/// A cancellation token, shared by its clones. The default token is never cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl Cancel {
+    /// A token that is cancelled explicitly, or after the timeout (if given).
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Cancel {
+            cancelled: Arc::default(),
+            deadline: timeout.map(|t| Instant::now() + t),
+        }
+    }
+
+    /// Cancels the token and all its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.deadline.is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// Fails if the token is cancelled: called regularly by long-running loops.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        match self.is_cancelled() {
+            true => Err(Cancelled),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+
+    use super::*;
+
+    #[test]
+    fn cancel_and_time_out() {
+        let cancel = Cancel::new(None);
+        let clone = cancel.clone();
+        assert_eq!(clone.check(), Ok(()));
+        cancel.cancel();
+        assert_eq!(clone.check(), Err(Cancelled));
+        assert!(Cancel::default().check().is_ok());
+
+        let cancel = Cancel::new(Some(Duration::ZERO));
+        let e = Err::<(), _>(cancel.check().unwrap_err())
+            .context("day 15 part 2")
+            .unwrap_err();
+        assert!(is_cancelled(&e));
+        assert!(!is_cancelled(&anyhow::anyhow!("timed out")));
+    }
+}
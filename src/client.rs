@@ -0,0 +1,378 @@
+//! Access to the Advent of Code website, authenticated with the session cookie of a
+//! logged-in browser.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use ureq::Agent;
+
+use crate::Part;
+
+/// Environment variable holding the session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable naming the file to read the session token from, if
+/// [`SESSION_VAR`] is not set (default: `~/.adventofcode.session`).
+pub const SESSION_FILE_VAR: &str = "AOC_SESSION_FILE";
+/// Environment variable overriding [`DEFAULT_BASE_URL`].
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const SESSION_FILE: &str = ".adventofcode.session";
+const YEAR: u16 = 2022;
+const USER_AGENT: &str = "github.com/kenohassler/aoc-2022";
+
+/// The session token from `$AOC_SESSION`, or else from the session file.
+pub fn session() -> Result<String> {
+    if let Ok(token) = env::var(SESSION_VAR) {
+        return Ok(token.trim().to_owned());
+    }
+    let path = match env::var_os(SESSION_FILE_VAR) {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let home = env::var_os("HOME").context("cannot locate the home directory")?;
+            Path::new(&home).join(SESSION_FILE)
+        }
+    };
+    match fs::read_to_string(&path) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_owned()),
+        Ok(_) => bail!("session file {} is empty", path.display()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => bail!(
+            "no session token: set {SESSION_VAR} or write it to {}",
+            path.display()
+        ),
+        Err(e) => Err(e).with_context(|| format!("cannot read {}", path.display())),
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let config = Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .build();
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            agent: config.into(),
+        }
+    }
+
+    /// A client for `$AOC_BASE_URL` (or the real website), using [`session`].
+    pub fn from_env() -> Result<Self> {
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+        Ok(Client::new(&base_url, &session()?))
+    }
+
+    /// The URL of a page of the given day, e.g. `/input`.
+    fn url(&self, day: u8, page: &str) -> String {
+        format!("{}/{YEAR}/day/{day}{page}", self.base_url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Submit an answer for one part of the given day.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Verdict> {
+        let url = self.url(day, "/answer");
+        let level = part.number().to_string();
+        let mut response = self
+            .agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .with_context(|| format!("cannot reach {url}"))?;
+        let status = response.status();
+        let body = response.body_mut().read_to_string()?;
+        if !status.is_success() {
+            let reason = body.lines().next().unwrap_or_default();
+            bail!("{url} returned {status}: {reason}");
+        }
+        Verdict::parse(&body)
+    }
+
+    /// Download the puzzle input of the given day.
+    pub fn input(&self, day: u8) -> Result<String> {
+        let url = self.url(day, "/input");
+        let mut response = self
+            .agent
+            .get(&url)
+            .header("Cookie", self.cookie())
+            .call()
+            .with_context(|| format!("cannot reach {url}"))?;
+        let status = response.status();
+        let body = response.body_mut().read_to_string()?;
+        if !status.is_success() {
+            let reason = body.lines().next().unwrap_or_default();
+            bail!("{url} returned {status}: {reason}");
+        }
+        Ok(body)
+    }
+}
+
+/// The website's response to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// A wrong answer, which locks further answers for `wait`.
+    Wrong {
+        hint: Option<Hint>,
+        wait: Duration,
+    },
+    /// The previous answer was too recent, try again after the given time.
+    TooRecent(Duration),
+    /// The part was solved before (or part 1 is still missing).
+    WrongLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    /// Reads the verdict from the text of the answer page.
+    fn parse(page: &str) -> Result<Self> {
+        if page.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if page.contains("That's not the right answer") {
+            let hint = if page.contains("your answer is too high") {
+                Some(Hint::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            // "please wait one minute before trying again", or "wait 5 minutes"
+            let wait = match between(page, "wait ", " before trying again") {
+                Some("one minute") => Duration::from_secs(60),
+                Some(minutes) => {
+                    let minutes: u64 = minutes.trim_end_matches(" minutes").parse()?;
+                    Duration::from_secs(minutes * 60)
+                }
+                None => Duration::from_secs(60),
+            };
+            Ok(Verdict::Wrong { hint, wait })
+        } else if page.contains("You gave an answer too recently") {
+            // "You have 1m 23s left to wait."
+            let left = between(page, "You have ", " left to wait")
+                .context("cannot find the remaining time")?;
+            let mut secs = 0;
+            for part in left.split_whitespace() {
+                secs += match part.split_at(part.len() - 1) {
+                    (n, "m") => n.parse::<u64>()? * 60,
+                    (n, "s") => n.parse::<u64>()?,
+                    _ => bail!("unexpected time: {left}"),
+                };
+            }
+            Ok(Verdict::TooRecent(Duration::from_secs(secs)))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Ok(Verdict::WrongLevel)
+        } else {
+            bail!("unexpected response: {}", page.trim())
+        }
+    }
+}
+
+/// The text between the first `start` and the following `end`.
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = text.split_once(start)?;
+    Some(rest.split_once(end)?.0)
+}
+
+/// The outcome of [`fetch_input`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was there already, nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Download the input of the given day to `path`, unless that file exists already.
+/// The client is only created for a download, so cached inputs need no session token.
+pub fn fetch_input(
+    day: u8,
+    path: &Path,
+    client: impl FnOnce() -> Result<Client>,
+) -> Result<Fetched> {
+    if path.exists() {
+        return Ok(Fetched::Cached(path.to_owned()));
+    }
+    let input = client()?.input(day)?;
+    if input.trim().is_empty() {
+        bail!("the input of day {day} is empty");
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    }
+    // write to a temporary file first, so an interrupted download is not cached
+    let partial = path.with_extension("part");
+    fs::write(&partial, input).with_context(|| format!("cannot write {}", partial.display()))?;
+    fs::rename(&partial, path).with_context(|| format!("cannot write {}", path.display()))?;
+    Ok(Fetched::Downloaded(path.to_owned()))
+}
+
+#[cfg(test)]
+pub(crate) mod stub {
+    //! A minimal HTTP server answering with canned responses, one per connection.
+
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// A request as received by the stub.
+    #[derive(Debug)]
+    pub struct Request {
+        /// e.g. `GET /2022/day/1/input`
+        pub line: String,
+        pub headers: Vec<String>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers.iter().find_map(|h| {
+                let (key, value) = h.split_once(':')?;
+                key.eq_ignore_ascii_case(name).then(|| value.trim())
+            })
+        }
+    }
+
+    /// Serve the given `(status, body)` responses, then return the requests received.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut headers = Vec::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end().to_owned();
+                    if header.is_empty() {
+                        break;
+                    }
+                    headers.push(header);
+                }
+                let mut request = Request {
+                    line: line.trim_end().to_owned(),
+                    headers,
+                    body: String::new(),
+                };
+                let len: usize = request
+                    .header("Content-Length")
+                    .map_or(0, |l| l.parse().unwrap());
+                let mut request_body = vec![0; len];
+                reader.read_exact(&mut request_body).unwrap();
+                request.body = String::from_utf8(request_body).unwrap();
+                requests.push(request);
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-2022-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetch_and_cache() {
+        let (url, server) = stub::serve(vec![(200, "1000\n2000\n")]);
+        let path = temp_dir("fetch").join("day1.txt");
+
+        let fetched = fetch_input(1, &path, || Ok(Client::new(&url, "s3cret"))).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        // the stub only answers once: a second download would fail
+        let fetched = fetch_input(1, &path, || bail!("no client needed")).unwrap();
+        assert_eq!(fetched, Fetched::Cached(path.clone()));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].line, "GET /2022/day/1/input HTTP/1.1");
+        assert_eq!(requests[0].header("Cookie"), Some("session=s3cret"));
+        assert_eq!(requests[0].header("User-Agent"), Some(USER_AGENT));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        let page = |text| format!("<html><main><article><p>{text}</p></article></main></html>");
+        let verdict = |text| Verdict::parse(&page(text)).unwrap();
+
+        assert_eq!(
+            verdict("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too high. Please wait one minute before trying again."),
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait: Duration::from_secs(60)
+            }
+        );
+        assert_eq!(
+            verdict("That's not the right answer. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."),
+            Verdict::Wrong {
+                hint: None,
+                wait: Duration::from_secs(300)
+            }
+        );
+        assert_eq!(
+            verdict("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait."),
+            Verdict::TooRecent(Duration::from_secs(83))
+        );
+        assert_eq!(
+            verdict("You don't seem to be solving the right level.  Did you already complete it?"),
+            Verdict::WrongLevel
+        );
+        assert!(Verdict::parse(&page("Something else")).is_err());
+    }
+
+    #[test]
+    fn failed_download_is_not_cached() {
+        let (url, server) = stub::serve(vec![(400, "Puzzle inputs differ by user.\n")]);
+        let path = temp_dir("fetch-fail").join("day2.txt");
+
+        let err = fetch_input(2, &path, || Ok(Client::new(&url, "expired"))).unwrap_err();
+        assert!(err.to_string().contains("Puzzle inputs differ by user."));
+        assert!(!path.exists());
+        server.join().unwrap();
+    }
+}
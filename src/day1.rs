@@ -0,0 +1,80 @@
+//! Day 1: Calorie Counting.
+
+use std::{fmt::Display, mem};
+
+use anyhow::{ensure, Context, Result};
+
+use crate::{parse, Params, ParseError, Solution};
+
+/// The calories of the snacks one elf carries.
+pub struct Elf(Vec<i32>);
+
+impl Elf {
+    fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    fn add(&mut self, snack: i32) {
+        self.0.push(snack);
+    }
+
+    pub fn snacks(&self) -> &[i32] {
+        &self.0
+    }
+
+    /// The calories of all snacks together.
+    pub fn calories(&self) -> i32 {
+        self.0.iter().sum()
+    }
+}
+
+fn get_elves(input: &str) -> Result<Vec<Elf>, ParseError> {
+    let mut elves = Vec::new();
+    let mut cur_elf = Elf::new();
+    for ll in parse::lines(Day1::DAY, input) {
+        if ll.text().is_empty() {
+            elves.push(mem::replace(&mut cur_elf, Elf::new()));
+        } else {
+            cur_elf.add(ll.parse(ll.text())?)
+        }
+    }
+    elves.push(cur_elf);
+    Ok(elves)
+}
+
+/// The most calories carried by one elf.
+pub fn max_elf(elves: &[Elf]) -> Result<i32> {
+    elves
+        .iter()
+        .map(|elf| elf.calories())
+        .max()
+        .context("no elves")
+}
+
+/// The calories carried by the three elves carrying the most.
+pub fn top3_elves(elves: &[Elf]) -> Result<i32> {
+    ensure!(elves.len() >= 3, "need at least three elves");
+    let mut calories: Vec<i32> = elves.iter().map(|elf| elf.calories()).collect();
+    calories.sort_unstable();
+    calories.reverse();
+    Ok(calories[0] + calories[1] + calories[2])
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<Elf>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_elves(input)
+    }
+
+    fn part1(elves: &Self::Input, _params: &Params) -> Result<impl Display> {
+        max_elf(elves)
+    }
+
+    fn part2(elves: &Self::Input, _params: &Params) -> Result<impl Display> {
+        top3_elves(elves)
+    }
+}
@@ -0,0 +1,182 @@
+//! Day 10: Cathode-Ray Tube.
+
+use anyhow::{ensure, Result};
+use itertools::Itertools;
+use log::debug;
+use std::fmt::Display;
+
+use crate::{
+    animate::{Animated, Animation},
+    debugger::{Debugged, Session},
+    parse, Params, ParseError, Part, Solution,
+};
+
+/// The value of the X register during every cycle, starting from cycle 0.
+#[derive(Clone)]
+pub struct Crt {
+    cycles: Vec<i32>,
+}
+
+impl Crt {
+    fn new(instructions: &str) -> Result<Self, ParseError> {
+        let mut x = 1;
+        let mut cycles = vec![x];
+
+        for ll in parse::lines(Day10::DAY, instructions) {
+            let mut words = ll.text().split_whitespace();
+            match words.next() {
+                Some("addx") => {
+                    cycles.push(x);
+                    cycles.push(x);
+                    let value = words
+                        .next()
+                        .ok_or_else(|| ll.error(ll.end(), "addx expects an immediate value"))?;
+                    x += ll.parse::<i32>(value)?;
+                }
+                Some("noop") => {
+                    cycles.push(x);
+                }
+                Some(e) => return Err(ll.error(e, "unsupported instruction")),
+                None => return Err(ll.error(ll.text(), "empty line")),
+            }
+        }
+        Ok(Self { cycles })
+    }
+
+    pub fn cycles(&self) -> &[i32] {
+        &self.cycles
+    }
+
+    /// The sum of the signal strengths during the given cycles.
+    pub fn sig_strength(&self, probes: &[usize]) -> Result<i32> {
+        let mut sum = 0;
+        for &cycle in probes {
+            ensure!(
+                cycle < self.cycles.len(),
+                "the program ends before cycle {cycle}"
+            );
+            let strength = self.cycles[cycle] * cycle as i32;
+            debug!("cycle {cycle}: strength {strength}");
+            sum += strength;
+        }
+        Ok(sum)
+    }
+
+    /// The screen after running the whole program.
+    pub fn draw(&self) -> String {
+        self.draw_until(self.cycles.len() - 1)
+    }
+
+    /// The screen after the given number of cycles.
+    fn draw_until(&self, cycle: usize) -> String {
+        let mut lines = Vec::new();
+        for iter in &self.cycles[1..=cycle].iter().chunks(40) {
+            let mut line = String::new();
+            for (i, x) in iter.enumerate() {
+                line.push(pixel(i, *x));
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+}
+
+/// Whether the sprite at `x` is lit when the beam draws column `i`.
+fn pixel(i: usize, x: i32) -> char {
+    let i: i32 = i.try_into().unwrap(); // lines are len 40, should never fail
+    if x == i || x - 1 == i || x + 1 == i {
+        '#'
+    } else {
+        '.'
+    }
+}
+
+/// The beam drawing the screen, one cycle at a time.
+struct Beam {
+    crt: Crt,
+    cycle: usize,
+}
+
+impl Animation for Beam {
+    fn frame(&self) -> String {
+        let x = self.crt.cycles[self.cycle];
+        let sprite: String = (0..40).map(|i| pixel(i, x)).collect();
+        let screen = self.crt.draw_until(self.cycle);
+        format!(
+            "cycle {:3}  X = {x:3}\nsprite: {sprite}\n\n{screen}\n",
+            self.cycle
+        )
+    }
+
+    fn step(&mut self) -> bool {
+        if self.cycle + 1 < self.crt.cycles.len() {
+            self.cycle += 1;
+            return true;
+        }
+        false
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Crt;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Crt::new(input)
+    }
+
+    fn part1(crt: &Self::Input, params: &Params) -> Result<impl Display> {
+        crt.sig_strength(&params.get::<Vec<usize>>("probes")?)
+    }
+
+    fn part2(crt: &Self::Input, _params: &Params) -> Result<impl Display> {
+        Ok(crt.draw())
+    }
+}
+
+impl Session for Beam {
+    fn unit(&self) -> &'static str {
+        "cycle"
+    }
+
+    fn step(&mut self) -> Result<bool> {
+        Ok(Animation::step(self))
+    }
+
+    fn show(&self) -> String {
+        self.frame()
+    }
+
+    /// The cycle, the X register during it, and the signal strength.
+    fn values(&self) -> Vec<(String, i64)> {
+        let cycle = self.cycle as i64;
+        let x = i64::from(self.crt.cycles[self.cycle]);
+        vec![
+            ("cycle".to_owned(), cycle),
+            ("x".to_owned(), x),
+            ("strength".to_owned(), cycle * x),
+        ]
+    }
+}
+
+impl Debugged for Day10 {
+    /// Both parts run the same program, so they share the session.
+    fn session(crt: &Self::Input, _part: Part, _params: &Params) -> Result<Box<dyn Session>> {
+        Ok(Box::new(Beam {
+            crt: crt.clone(),
+            cycle: 0,
+        }))
+    }
+}
+
+impl Animated for Day10 {
+    /// Both parts watch the same program, so they share the animation.
+    fn animation(crt: &Self::Input, _part: Part, _params: &Params) -> Result<Box<dyn Animation>> {
+        Ok(Box::new(Beam {
+            crt: crt.clone(),
+            cycle: 0,
+        }))
+    }
+}
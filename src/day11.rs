@@ -0,0 +1,564 @@
+//! Day 11: Monkey in the Middle.
+
+use std::{
+    cell::RefCell, collections::VecDeque, fmt, num::ParseIntError, ops::DerefMut, str::FromStr,
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use itertools::Itertools;
+use log::{debug, log_enabled, trace, Level};
+
+use crate::{
+    cancel::Cancel,
+    debugger::{Debugged, Session},
+    differential::Reference,
+    parse::{self, Line},
+    Params, ParseError, Part, Solution,
+};
+
+#[derive(Clone)]
+struct Item(u64);
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone)]
+enum Operand {
+    OldValue,
+    Immediate(u64),
+}
+
+impl FromStr for Operand {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "old" => Ok(Self::OldValue),
+            num => Ok(Self::Immediate(num.parse()?)),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Operator {
+    Plus,
+    Times,
+}
+
+impl FromStr for Operator {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "*" => Ok(Self::Times),
+            "+" => Ok(Self::Plus),
+            e => Err(anyhow!("unsupported operator: {e}")),
+        }
+    }
+}
+
+/// A monkey, with the items it holds and its rules for throwing them.
+#[derive(Clone)]
+pub struct Monkey {
+    items: VecDeque<Item>,
+    left: Operand,
+    right: Operand,
+    op: Operator,
+    divisor: u64,
+    monkey_true: usize,
+    monkey_false: usize,
+    item_counter: u64,
+}
+
+impl Monkey {
+    /// Parses the six lines describing monkey number `idx` (of `count`).
+    fn new<'a>(block: &[Line<'a>], idx: usize, count: usize) -> Result<Self, ParseError> {
+        let mut lines = block.iter().copied();
+        let mut line_helper = || {
+            let ll = match lines.next() {
+                Some(ll) => ll,
+                None => {
+                    let last = block[block.len() - 1];
+                    return Err(last.error(last.end(), "expected six input lines per monkey"));
+                }
+            };
+            let (_, rest) = ll.split_once(ll.text(), ":")?;
+            Ok((ll, rest))
+        };
+        let word = |ll: &Line<'a>, word: Option<&'a str>, what: &str| {
+            word.ok_or_else(|| ll.error(ll.end(), format!("missing {what}")))
+        };
+        let target = |ll: &Line<'a>, text: &'a str| {
+            let w = word(ll, text.split_ascii_whitespace().last(), "target monkey")?;
+            let target = ll.parse::<usize>(w)?;
+            if target >= count || target == idx {
+                return Err(ll.error(w, format!("expected another monkey below {count}")));
+            }
+            Ok(target)
+        };
+
+        // first line: monkey ID
+        let (ll, _) = line_helper()?;
+        let (_, id) = ll.split_once(ll.text().trim_end_matches(':'), " ")?;
+        if ll.parse::<usize>(id)? != idx {
+            return Err(ll.error(id, "expected monkeys in ascending order"));
+        }
+
+        // second line: items
+        let (ll, text) = line_helper()?;
+        let items = text
+            .split(',')
+            .map(|num| ll.parse(num.trim()).map(Item))
+            .collect::<Result<VecDeque<Item>, _>>()?;
+
+        // third line: operation
+        let (ll, text) = line_helper()?;
+        let mut op_text = text.split_ascii_whitespace();
+        let left = ll.parse(word(&ll, op_text.nth(2), "lhs")?)?;
+        let op = ll.parse(word(&ll, op_text.next(), "op")?)?;
+        let right = ll.parse(word(&ll, op_text.next(), "rhs")?)?;
+
+        // fourth line: test
+        let (ll, text) = line_helper()?;
+        let divisor = word(&ll, text.split_ascii_whitespace().nth(2), "divisor")?;
+        let divisor = match ll.parse(divisor)? {
+            0 => return Err(ll.error(divisor, "divisor must be positive")),
+            d => d,
+        };
+
+        // fifth line: monkey_true
+        let (ll, text) = line_helper()?;
+        let monkey_true = target(&ll, text)?;
+
+        // sixth line: monkey_false
+        let (ll, text) = line_helper()?;
+        let monkey_false = target(&ll, text)?;
+
+        Ok(Monkey {
+            items,
+            left,
+            right,
+            op,
+            divisor,
+            monkey_true,
+            monkey_false,
+            item_counter: 0,
+        })
+    }
+
+    /// The worry levels of the items the monkey holds.
+    pub fn items(&self) -> impl Iterator<Item = u64> + '_ {
+        self.items.iter().map(|i| i.0)
+    }
+
+    /// The monkey tests whether worry levels are divisible by this.
+    pub fn divisor(&self) -> u64 {
+        self.divisor
+    }
+
+    /// The number of items the monkey has inspected so far.
+    pub fn inspected(&self) -> u64 {
+        self.item_counter
+    }
+
+    fn operation(&self, item: &mut Item) -> Result<()> {
+        let left = match self.left {
+            Operand::OldValue => item.0,
+            Operand::Immediate(num) => num,
+        };
+        let right = match self.right {
+            Operand::OldValue => item.0,
+            Operand::Immediate(num) => num,
+        };
+        item.0 = match self.op {
+            Operator::Times => left.checked_mul(right),
+            Operator::Plus => left.checked_add(right),
+        }
+        .context("worry level overflows")?;
+        Ok(())
+    }
+
+    fn test(&self, item: &mut Item, modulus: Option<u64>) -> bool {
+        // modular arithmetic limits size
+        if let Some(modulus) = modulus {
+            item.0 %= modulus;
+        }
+        // the actual test
+        item.0.is_multiple_of(self.divisor)
+    }
+
+    fn process_items(
+        &mut self,
+        all_monkeys: &[RefCell<Monkey>],
+        modulus: Option<u64>,
+    ) -> Result<()> {
+        while let Some(mut item) = self.items.pop_front() {
+            // perform the monkey's calculation
+            self.operation(&mut item)?;
+
+            // decrease worry level (for part 1)
+            if modulus.is_none() {
+                item.0 /= 3u64;
+            }
+
+            // test divisibility (does modular reduction as well)
+            if self.test(&mut item, modulus) {
+                all_monkeys[self.monkey_true]
+                    .borrow_mut()
+                    .items
+                    .push_back(item);
+            } else {
+                all_monkeys[self.monkey_false]
+                    .borrow_mut()
+                    .items
+                    .push_back(item);
+            }
+
+            self.item_counter += 1;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Monkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut items = self.items.iter();
+        if let Some(mut last) = items.next() {
+            for cur in items {
+                write!(f, "{last}, ")?;
+                last = cur;
+            }
+            write!(f, "{last}")?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(monkeys: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let rounds = params.get("part1_rounds")?;
+        monkey_business(inspections(monkeys, rounds, true, params.cancel())?)
+    }
+
+    fn part2(monkeys: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let rounds = params.get("part2_rounds")?;
+        monkey_business(inspections(monkeys, rounds, false, params.cancel())?)
+    }
+}
+
+/// How many items each monkey inspects in the given number of rounds, with the worry
+/// levels divided by three after each inspection if there is `relief`. Fails if
+/// cancelled, or if the worry levels (or the divisors' product) overflow.
+pub fn inspections(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: bool,
+    cancel: &Cancel,
+) -> Result<Vec<u64>> {
+    let mut game = Rounds::new(monkeys, relief)?;
+    for _ in 0..rounds {
+        cancel.check()?;
+        game.play()?;
+    }
+    let inspected = game.inspected();
+    for (i, count) in inspected.iter().enumerate() {
+        debug!("Monkey {i} inspected items {count} times.");
+    }
+    Ok(inspected)
+}
+
+/// The product of the two highest inspection counts.
+pub fn monkey_business(counts: Vec<u64>) -> Result<u64> {
+    ensure!(counts.len() >= 2, "need >= 2 monkeys for monkey business");
+    let mut sorted = counts.iter().sorted_unstable().rev();
+    let first = sorted.next().unwrap();
+    let second = sorted.next().unwrap();
+
+    first
+        .checked_mul(*second)
+        .context("monkey business overflows")
+}
+
+fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let lines: Vec<Line> = parse::lines(Day11::DAY, input).collect();
+    let blocks: Vec<&[Line]> = lines
+        .split(|ll| ll.text().is_empty())
+        .filter(|block| !block.is_empty())
+        .collect();
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(idx, block)| Monkey::new(block, idx, blocks.len()))
+        .collect()
+}
+
+/// Euclidean algorithm for gcd, used as proxy for least common multiple
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let tmp = b;
+        b = a % b;
+        a = tmp;
+    }
+    a
+}
+
+/// The monkeys throwing items to each other, round after round.
+struct Rounds {
+    monkeys: Vec<RefCell<Monkey>>,
+    /// Worry levels are kept modulo this without relief
+    modulus: Option<u64>,
+    round: usize,
+}
+
+impl Rounds {
+    /// Fresh copies of the monkeys, before the first round.
+    fn new(monkeys: &[Monkey], relief: bool) -> Result<Self> {
+        let gcd = monkeys.iter().map(|m| m.divisor).reduce(gcd).unwrap_or(1);
+        let modulus = match relief {
+            // fun fact: the divisors are all prime, so gcd is always 1 here -.-
+            true => None,
+            false => {
+                let product = monkeys
+                    .iter()
+                    .try_fold(1u64, |product, m| product.checked_mul(m.divisor))
+                    .context("the divisors' product overflows")?;
+                Some(product / gcd)
+            }
+        };
+        Ok(Rounds {
+            monkeys: monkeys.iter().cloned().map(RefCell::new).collect(),
+            modulus,
+            round: 0,
+        })
+    }
+
+    fn play(&mut self) -> Result<()> {
+        for monkey in &self.monkeys {
+            monkey
+                .borrow_mut()
+                .deref_mut()
+                .process_items(&self.monkeys, self.modulus)?;
+        }
+        self.round += 1;
+
+        if log_enabled!(Level::Trace) {
+            trace!(
+                "after round {}, the monkeys are holding items with these worry levels:",
+                self.round
+            );
+            for (i, monkey) in self.monkeys.iter().enumerate() {
+                trace!("monkey {i}: {}", monkey.borrow());
+            }
+        }
+        Ok(())
+    }
+
+    /// How many items each monkey has inspected so far.
+    fn inspected(&self) -> Vec<u64> {
+        self.monkeys
+            .iter()
+            .map(|m| m.borrow().item_counter)
+            .collect()
+    }
+}
+
+impl Debugged for Day11 {
+    /// Steps through the rounds of the part.
+    fn session(monkeys: &Self::Input, part: Part, params: &Params) -> Result<Box<dyn Session>> {
+        let (total, relief) = match part {
+            Part::One => (params.get("part1_rounds")?, true),
+            Part::Two => (params.get("part2_rounds")?, false),
+        };
+        Ok(Box::new(Game {
+            rounds: Rounds::new(monkeys, relief)?,
+            total,
+        }))
+    }
+}
+
+/// The rounds played for one part.
+struct Game {
+    rounds: Rounds,
+    total: usize,
+}
+
+impl Session for Game {
+    fn unit(&self) -> &'static str {
+        "round"
+    }
+
+    fn step(&mut self) -> Result<bool> {
+        if self.rounds.round == self.total {
+            return Ok(false);
+        }
+        self.rounds.play()?;
+        Ok(true)
+    }
+
+    fn show(&self) -> String {
+        let mut state = String::new();
+        for (i, monkey) in self.rounds.monkeys.iter().enumerate() {
+            let monkey = monkey.borrow();
+            let line = format!("Monkey {i} ({} inspected): {monkey}", monkey.item_counter);
+            state += line.trim_end();
+            state.push('\n');
+        }
+        state
+    }
+
+    /// The round, and how many items each monkey has inspected and holds.
+    fn values(&self) -> Vec<(String, i64)> {
+        let mut values = vec![("round".to_owned(), self.rounds.round as i64)];
+        for (i, monkey) in self.rounds.monkeys.iter().enumerate() {
+            let monkey = monkey.borrow();
+            values.push((format!("inspected{i}"), monkey.item_counter as i64));
+            values.push((format!("items{i}"), monkey.items.len() as i64));
+        }
+        values
+    }
+}
+
+impl Reference for Day11 {
+    /// Keeps the exact worry levels, failing instead of overflowing.
+    fn reference1(monkeys: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let mut items: Vec<Vec<u128>> = monkeys
+            .iter()
+            .map(|m| m.items.iter().map(|i| u128::from(i.0)).collect())
+            .collect();
+        let mut counts = vec![0u64; monkeys.len()];
+        for _round in 0..params.get("part1_rounds")? {
+            for (i, monkey) in monkeys.iter().enumerate() {
+                for old in std::mem::take(&mut items[i]) {
+                    let value = |operand: &Operand| match operand {
+                        Operand::OldValue => old,
+                        Operand::Immediate(num) => u128::from(*num),
+                    };
+                    let (left, right) = (value(&monkey.left), value(&monkey.right));
+                    let new = match monkey.op {
+                        Operator::Plus => left.checked_add(right),
+                        Operator::Times => left.checked_mul(right),
+                    }
+                    .context("worry level overflows")?
+                        / 3;
+                    let target = match new % u128::from(monkey.divisor) == 0 {
+                        true => monkey.monkey_true,
+                        false => monkey.monkey_false,
+                    };
+                    items[target].push(new);
+                    counts[i] += 1;
+                }
+            }
+        }
+        top_two(counts)
+    }
+
+    /// Tracks every worry level as its residues modulo each monkey's divisor.
+    fn reference2(monkeys: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let divisors: Vec<u64> = monkeys.iter().map(|m| m.divisor).collect();
+        ensure!(divisors.iter().all(|&d| d > 0), "divisor 0");
+        let mut items: Vec<Vec<Vec<u64>>> = monkeys
+            .iter()
+            .map(|m| {
+                m.items
+                    .iter()
+                    .map(|i| divisors.iter().map(|d| i.0 % d).collect())
+                    .collect()
+            })
+            .collect();
+        let mut counts = vec![0u64; monkeys.len()];
+        for _round in 0..params.get::<usize>("part2_rounds")? {
+            for (i, monkey) in monkeys.iter().enumerate() {
+                for mut residues in std::mem::take(&mut items[i]) {
+                    for (r, &d) in residues.iter_mut().zip(&divisors) {
+                        let (old, d) = (u128::from(*r), u128::from(d));
+                        let value = |operand: &Operand| match operand {
+                            Operand::OldValue => old,
+                            Operand::Immediate(num) => u128::from(*num) % d,
+                        };
+                        let (left, right) = (value(&monkey.left), value(&monkey.right));
+                        let new = match monkey.op {
+                            Operator::Plus => left + right,
+                            Operator::Times => left * right,
+                        };
+                        *r = (new % d) as u64;
+                    }
+                    let target = match residues[i] == 0 {
+                        true => monkey.monkey_true,
+                        false => monkey.monkey_false,
+                    };
+                    items[target].push(residues);
+                    counts[i] += 1;
+                }
+            }
+        }
+        top_two(counts)
+    }
+}
+
+/// The product of the two highest counts, by repeatedly taking the maximum.
+fn top_two(mut counts: Vec<u64>) -> Result<u64> {
+    ensure!(counts.len() >= 2, "need >= 2 monkeys for monkey business");
+    let mut product = 1;
+    for _ in 0..2 {
+        let (idx, &max) = counts.iter().enumerate().max_by_key(|(_, &c)| c).unwrap();
+        product *= max;
+        counts.remove(idx);
+    }
+    Ok(product)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    #[test]
+    fn snapshot_rounds() {
+        let monkeys = Day11::parse(&crate::example(11).unwrap()).unwrap();
+        let mut game = Rounds::new(&monkeys, true).unwrap();
+        let mut rounds = String::new();
+        for round in 1..=3 {
+            game.play().unwrap();
+            rounds += &format!("after round {round}:\n");
+            for (i, monkey) in game.monkeys.iter().enumerate() {
+                rounds += &format!("Monkey {i}: {}\n", monkey.borrow());
+            }
+        }
+        assert_snapshot("day11_rounds", &rounds);
+    }
+
+    #[test]
+    fn bad_monkeys() {
+        let example = crate::example(11).unwrap();
+        let err = Day11::parse(&example.replace("by 23", "by 0"))
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column), (4, 22));
+        assert_eq!(err.message, "divisor must be positive");
+
+        let squares = example
+            .replacen("79", "4294967296", 1)
+            .replacen("old * 19", "old * old", 1);
+        let monkeys = Day11::parse(&squares).unwrap();
+        let err = inspections(&monkeys, 1, true, &Cancel::default()).unwrap_err();
+        assert_eq!(err.to_string(), "worry level overflows");
+
+        let big = example.replace("by 23", "by 4294967291");
+        let monkeys = Day11::parse(&big.replace("by 19", "by 4294967279")).unwrap();
+        let err = inspections(&monkeys, 1, false, &Cancel::default()).unwrap_err();
+        assert_eq!(err.to_string(), "the divisors' product overflows");
+        assert!(monkey_business(vec![1 << 32, 1 << 32]).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Day 12: Hill Climbing Algorithm.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+};
+
+use anyhow::{ensure, Context, Result};
+use itertools::Itertools;
+use log::debug;
+
+use crate::{
+    grid::{self, Pos},
+    image::{Image, Pictured, Rgb},
+    Params, ParseError, Part, Solution,
+};
+
+#[derive(Clone)]
+struct Node {
+    elevation: u8,
+    next: Option<Pos>,
+}
+
+impl Node {
+    fn new(elevation: u8) -> Self {
+        Node {
+            elevation,
+            next: None,
+        }
+    }
+}
+
+/// The heightmap, from the start `S` to the destination `E`.
+#[derive(Clone)]
+pub struct Grid {
+    nodes: grid::Grid<Node>,
+    start: Pos,
+    dest: Pos,
+}
+
+impl Grid {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let mut start = None;
+        let mut dest = None;
+
+        let nodes = grid::Grid::parse(Day12::DAY, input, |pos, c| {
+            match c {
+                // either lowercase, current position (S) or destination (E)
+                'S' => {
+                    ensure!(start.is_none(), "only one start node allowed");
+                    start = Some(pos);
+                    Ok(Node::new(1))
+                }
+                'E' => {
+                    ensure!(dest.is_none(), "only one destination node allowed");
+                    dest = Some(pos);
+                    Ok(Node::new(26))
+                }
+                lc => {
+                    ensure!(lc.is_ascii_lowercase(), "non-allowed char");
+                    let b = lc as u8 - 96;
+                    Ok(Node::new(b))
+                }
+            }
+        })?;
+
+        let missing = |what| ParseError::end_of_input(Day12::DAY, input, format!("no {what} node"));
+        Ok(Grid {
+            nodes,
+            dest: dest.ok_or_else(|| missing("destination"))?,
+            start: start.ok_or_else(|| missing("start"))?,
+        })
+    }
+
+    pub fn start(&self) -> Pos {
+        self.start
+    }
+
+    pub fn dest(&self) -> Pos {
+        self.dest
+    }
+
+    /// The elevation at `pos`, from 1 (`a`) to 26 (`z`).
+    pub fn elevation(&self, pos: Pos) -> Option<u8> {
+        self.nodes.get(pos).map(|n| n.elevation)
+    }
+
+    /// Finds the shortest paths from every position to the destination, walking
+    /// backwards from it. Returns the closest position of elevation `a`.
+    pub fn inplace_bfs(&mut self) -> Option<Pos> {
+        let mut q = VecDeque::new();
+        q.push_back(self.dest);
+        let mut shortest = None;
+
+        while let Some(pos) = q.pop_front() {
+            if self.nodes[pos].elevation == 1 && shortest.is_none() {
+                shortest = Some(pos);
+            }
+
+            let neighbours: Vec<Pos> = self.nodes.neighbours4(pos).collect();
+            for n in neighbours {
+                if self.nodes[n].elevation + 1 >= self.nodes[pos].elevation
+                    && self.nodes[n].next.is_none()
+                {
+                    self.nodes[n].next = Some(pos);
+                    q.push_back(n);
+                }
+            }
+        }
+        shortest
+    }
+
+    /// The number of steps from `from` to the destination, after [`Grid::inplace_bfs`].
+    pub fn path(&self, from: Pos) -> usize {
+        let path = self.positions(from);
+        debug!("start --> [{}] <-- dest", path.iter().join(", "));
+        path.len()
+    }
+
+    /// The positions on the path from `from`, excluding the destination.
+    pub fn positions(&self, from: Pos) -> Vec<Pos> {
+        let mut path = vec![from];
+        let mut cur = path.last().unwrap();
+        while let Some(pos) = &self.nodes[*cur].next {
+            if *pos == self.dest {
+                break;
+            }
+            path.push(*pos);
+            cur = path.last().unwrap();
+        }
+        path
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::new(input)
+    }
+
+    fn part1(grid: &Self::Input, _params: &Params) -> Result<impl fmt::Display> {
+        let mut grid = grid.clone();
+        grid.inplace_bfs().context("no path found")?;
+        ensure!(
+            grid.nodes[grid.start].next.is_some(),
+            "no path from the start"
+        );
+        Ok(grid.path(grid.start))
+    }
+
+    fn part2(grid: &Self::Input, _params: &Params) -> Result<impl fmt::Display> {
+        let mut grid = grid.clone();
+        let shortest = grid.inplace_bfs().context("no path found")?;
+        Ok(grid.path(shortest))
+    }
+}
+
+impl Pictured for Day12 {
+    /// The heightmap from dark green (a) to white (z), with the path in red.
+    fn image(grid: &Self::Input, part: Part, _params: &Params) -> Result<Image> {
+        let mut grid = grid.clone();
+        let shortest = grid.inplace_bfs().context("no path found")?;
+        let from = match part {
+            Part::One => {
+                ensure!(
+                    grid.nodes[grid.start].next.is_some(),
+                    "no path from the start"
+                );
+                grid.start
+            }
+            Part::Two => shortest,
+        };
+        let path: HashSet<Pos> = grid.positions(from).into_iter().collect();
+        let image = Image::from_grid(&grid.nodes, |pos, node| {
+            if pos == grid.dest || path.contains(&pos) {
+                Rgb::RED
+            } else {
+                let height = f64::from(node.elevation - 1) / 25.0;
+                Rgb(20, 60, 20).mix(Rgb::WHITE, height)
+            }
+        });
+        Ok(image.with_scale(6))
+    }
+}
@@ -0,0 +1,225 @@
+//! Day 13: Distress Signal.
+
+use anyhow::{Context, Result};
+use std::{cmp::Ordering, fmt, mem, str::FromStr};
+use thiserror::Error;
+
+use crate::{parse, Params, ParseError, Solution};
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Integer(u32),
+    List(Vec<Value>),
+}
+
+impl From<u32> for Value {
+    fn from(i: u32) -> Self {
+        Self::Integer(i)
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Integer(l0), Self::Integer(r0)) => l0 == r0,
+            (Self::List(l0), Self::List(r0)) => l0 == r0,
+            (Self::Integer(l0), Self::List(r0)) => vec![Self::Integer(*l0)] == *r0,
+            (Self::List(l0), Self::Integer(r0)) => *l0 == vec![Self::Integer(*r0)],
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Integer(l0), Self::Integer(r0)) => l0.cmp(r0),
+            (Self::List(l0), Self::List(r0)) => l0.cmp(r0),
+            (Self::Integer(l0), Self::List(r0)) => vec![Self::Integer(*l0)].cmp(r0),
+            (Self::List(l0), Self::Integer(r0)) => l0.cmp(&vec![Self::Integer(*r0)]),
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let js = json::parse(s)?;
+        if js.is_number() {
+            Ok(Value::Integer(
+                js.as_u32().ok_or(ParseValueError::InvalidInteger)?,
+            ))
+        } else if js.is_array() {
+            let mut vals = Vec::new();
+            for item in js.members() {
+                vals.push(item.to_string().parse::<Value>()?);
+            }
+            Ok(Value::List(vals))
+        } else {
+            Err(ParseValueError::InvalidType)
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Integer(i) => write!(f, "{i}"),
+            Value::List(l) => {
+                write!(f, "[")?;
+                if let Some(last) = l.last() {
+                    for elem in &l[0..l.len() - 1] {
+                        write!(f, "{},", elem)?;
+                    }
+                    write!(f, "{}", last)?;
+                }
+                write!(f, "]")?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ParseValueError {
+    #[error("the input is not valid JSON")]
+    JSONError(#[from] json::Error),
+    #[error("elements can only be numbers or arrays")]
+    InvalidType,
+    #[error("not an integer")]
+    InvalidInteger,
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<Vec<Value>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_pairs(input)
+    }
+
+    fn part1(pairs: &Self::Input, _params: &Params) -> Result<impl fmt::Display> {
+        Ok(right_order_sum(pairs))
+    }
+
+    fn part2(pairs: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let dividers = params
+            .get::<Vec<String>>("dividers")?
+            .iter()
+            .map(|d| d.parse().with_context(|| format!("invalid divider {d}")))
+            .collect::<Result<Vec<Value>>>()?;
+        Ok(divider_positions(pairs, &dividers)
+            .iter()
+            .product::<usize>())
+    }
+}
+
+fn parse_pairs(input: &str) -> Result<Vec<Vec<Value>>, ParseError> {
+    let mut pairs = Vec::new();
+    let mut pair = Vec::new();
+    for ll in parse::lines(Day13::DAY, input) {
+        if ll.text().is_empty() {
+            pairs.push(mem::take(&mut pair));
+        } else {
+            pair.push(ll.parse::<Value>(ll.text())?);
+        }
+    }
+    if !pair.is_empty() {
+        pairs.push(pair);
+    }
+    Ok(pairs)
+}
+
+/// The sum of the (1-based) indices of the pairs in the right order.
+pub fn right_order_sum(pairs: &[Vec<Value>]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.first() < p.last())
+        .map(|(i, _)| i + 1)
+        .sum()
+}
+
+/// The (1-based) positions of the divider packets (`[[2]]` and `[[6]]` in the
+/// puzzle) once they are sorted in with all other packets.
+pub fn divider_positions(pairs: &[Vec<Value>], dividers: &[Value]) -> Vec<usize> {
+    let mut list = pairs.iter().flatten().cloned().collect::<Vec<Value>>();
+
+    // insert dividers somewhere
+    list.extend(dividers.iter().cloned());
+
+    list.sort();
+    dividers
+        .iter()
+        .map(|d| list.iter().position(|v| v == d).unwrap() + 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Day13, Value};
+    use crate::{snapshot::assert_snapshot, Solution};
+
+    #[test]
+    fn compare_lists() {
+        let a = Value::List(vec![1.into(), 1.into(), 3.into(), 1.into(), 1.into()]);
+        let b = Value::List(vec![1.into(), 1.into(), 5.into(), 1.into(), 1.into()]);
+        assert_ne!(a, b);
+        assert!(a < b);
+    }
+
+    #[test]
+    fn compare_len() {
+        let a = Value::List(vec![7.into(), 7.into(), 7.into(), 7.into()]);
+        let b = Value::List(vec![7.into(), 7.into(), 7.into()]);
+        assert_ne!(a, b);
+        assert!(a > b);
+        println!("{a} > {b}");
+    }
+
+    #[test]
+    fn compare_one_empty() {
+        let a = Value::List(vec![]);
+        let b = Value::List(vec![3.into()]);
+        assert_ne!(a, b);
+        assert!(a < b);
+        println!("{a} < {b}");
+    }
+
+    #[test]
+    fn compare_empty() {
+        let a = Value::List(vec![Value::List(vec![Value::List(vec![])])]);
+        let b = Value::List(vec![Value::List(vec![])]);
+        assert_ne!(a, b);
+        assert!(a > b);
+        println!("{a} > {b}");
+    }
+
+    #[test]
+    fn parse() {
+        let input = "[[]]";
+        let output = Value::List(vec![Value::List(vec![])]);
+        assert_eq!(input.parse::<Value>().unwrap(), output);
+        println!("{output}");
+    }
+
+    #[test]
+    fn snapshot_sorted() {
+        let pairs = Day13::parse(&crate::example(13).unwrap()).unwrap();
+        let mut packets: Vec<Value> = pairs.into_iter().flatten().collect();
+        packets.extend(["[[2]]", "[[6]]"].map(|p| p.parse().unwrap()));
+        packets.sort();
+        let sorted: String = packets.iter().map(|p| format!("{p}\n")).collect();
+        assert_snapshot("day13_sorted", &sorted);
+    }
+}
@@ -0,0 +1,389 @@
+//! Day 14: Regolith Reservoir.
+
+use anyhow::Result;
+use itertools::Itertools;
+use log::trace;
+use std::{
+    cmp::{max, min},
+    fmt,
+};
+
+use crate::{
+    animate::{Animated, Animation},
+    cancel::{Cancel, Cancelled},
+    debugger::{Debugged, Session},
+    geom::BoundingBox,
+    grid::{self, Pos},
+    image::{Image, Pictured, Rgb},
+    parse, Params, ParseError, Part, Solution,
+};
+
+/// Where the sand is poured in.
+const SOURCE: Pos = Pos::new(500, 0);
+
+/// What is at a position of the cave.
+#[derive(Clone)]
+pub enum Point {
+    Air,
+    Rock,
+    SandRest,
+    SandSource,
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Point::Air => ".",
+            Point::Rock => "#",
+            Point::SandRest => "o",
+            Point::SandSource => "+",
+        };
+        f.write_str(c)
+    }
+}
+
+/// A slice of the cave, from x = 0 to the rightmost rock.
+#[derive(Clone)]
+pub struct Grid(grid::Grid<Point>);
+
+impl Grid {
+    fn new() -> Self {
+        let mut grid = Grid(grid::Grid::new(SOURCE.x + 1, 1, Point::Air));
+        grid.0[SOURCE] = Point::SandSource;
+        grid
+    }
+
+    pub fn at(&self, x: usize, y: usize) -> Option<&Point> {
+        self.0.get(Pos { x, y })
+    }
+
+    fn at_mut(&mut self, x: usize, y: usize) -> Option<&mut Point> {
+        self.0.get_mut(Pos { x, y })
+    }
+
+    fn add_line(&mut self, from: Pos, to: Pos) {
+        if from.x != to.x {
+            // assuming from.y == to.y here (no diagonal lines)
+            let start = min(from.x, to.x);
+            let end = max(from.x, to.x);
+            for i in start..end + 1 {
+                self.add_point(i, from.y)
+            }
+        } else {
+            // x coords equal
+            let start = min(from.y, to.y);
+            let end = max(from.y, to.y);
+            for i in start..end + 1 {
+                self.add_point(from.x, i)
+            }
+        }
+    }
+
+    /// Extend the grid right and down so that it contains (x, y).
+    fn extend_to(&mut self, x: usize, y: usize) {
+        if !self.0.contains(Pos { x, y }) {
+            let width = self.0.width().max(x + 1);
+            let height = self.0.height().max(y + 1);
+            self.0.resize(width, height, Point::Air);
+        }
+    }
+
+    fn add_point(&mut self, x: usize, y: usize) {
+        self.extend_to(x, y);
+        *self.at_mut(x, y).unwrap() = Point::Rock;
+    }
+
+    /// Simulate one unit of sand until it comes to rest.
+    /// Returns the resting position, or None if the sand falls into the void.
+    fn simulate_step(&self) -> Option<Pos> {
+        let mut sand_pos = SOURCE;
+        loop {
+            // try down
+            match self.at(sand_pos.x, sand_pos.y + 1) {
+                Some(Point::Air) => {
+                    sand_pos.y += 1;
+                }
+                Some(Point::SandRest) | Some(Point::Rock) => {
+                    // try down-left
+                    match self.at(sand_pos.x - 1, sand_pos.y + 1) {
+                        Some(Point::Air) => {
+                            sand_pos.x -= 1;
+                            sand_pos.y += 1;
+                        }
+                        Some(Point::SandRest) | Some(Point::Rock) => {
+                            // try down-right
+                            match self.at(sand_pos.x + 1, sand_pos.y + 1) {
+                                Some(Point::Air) => {
+                                    sand_pos.x += 1;
+                                    sand_pos.y += 1;
+                                }
+                                Some(Point::SandRest) | Some(Point::Rock) => {
+                                    // sand comes to rest
+                                    return Some(sand_pos);
+                                }
+                                Some(Point::SandSource) => {
+                                    unreachable!("sand source cannot be below the falling sand")
+                                }
+                                None => {
+                                    // infinite fall, terminate
+                                    return None;
+                                }
+                            }
+                        }
+                        Some(Point::SandSource) => {
+                            unreachable!("sand source cannot be below the falling sand")
+                        }
+                        None => {
+                            // infinite fall, terminate
+                            return None;
+                        }
+                    }
+                }
+                Some(Point::SandSource) => {
+                    unreachable!("sand source cannot be below the falling sand")
+                }
+                None => {
+                    // infinite fall, terminate
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Find the minimum x position that is not air (used for pretty-printing).
+    fn x_min(&self) -> usize {
+        let start_idx = self
+            .0
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .find_or_last(|(_, p)| !matches!(p, Point::Air))
+                    .map(|(i, _)| i)
+                    .unwrap()
+            })
+            .min()
+            .unwrap();
+        start_idx
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start_idx = self.x_min();
+
+        for row in self.0.rows() {
+            for p in row.iter().skip(start_idx) {
+                write!(f, "{p}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        build_grid(input)
+    }
+
+    fn part1(g: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        Ok(simulate(g.clone(), params.cancel())?)
+    }
+
+    fn part2(g: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        Ok(simulate_finite(g.clone(), params.cancel())?)
+    }
+}
+
+impl Pictured for Day14 {
+    /// The cave once the sand stopped: rock in grey and sand in yellow.
+    fn image(g: &Self::Input, part: Part, _params: &Params) -> Result<Image> {
+        let mut sand = Pouring::new(g.clone(), part == Part::Two);
+        while sand.pour() {}
+        let grid = &sand.grid.0;
+        let x_min = sand.grid.x_min();
+        let image = Image::from_fn(grid.width() - x_min, grid.height(), |pos| {
+            match grid[Pos::new(pos.x + x_min, pos.y)] {
+                Point::Air => Rgb(20, 20, 30),
+                Point::Rock => Rgb(130, 130, 130),
+                Point::SandRest => Rgb(230, 200, 110),
+                Point::SandSource => Rgb::RED,
+            }
+        });
+        Ok(image.with_scale(4))
+    }
+}
+
+impl Animated for Day14 {
+    fn animation(g: &Self::Input, part: Part, _params: &Params) -> Result<Box<dyn Animation>> {
+        Ok(Box::new(Pouring::new(g.clone(), part == Part::Two)))
+    }
+}
+
+/// The floor of part 2, which is extended whenever sand would fall past its ends.
+struct Floor {
+    y: usize,
+    xmin: usize,
+    xmax: usize,
+}
+
+/// Sand poured into the cave, one unit at a time.
+struct Pouring {
+    grid: Grid,
+    floor: Option<Floor>,
+    /// Units of sand at rest
+    count: usize,
+    /// Where the last unit of sand came to rest
+    last: Option<Pos>,
+    done: bool,
+}
+
+impl Pouring {
+    fn new(mut grid: Grid, with_floor: bool) -> Self {
+        let floor = with_floor.then(|| Floor {
+            y: grid.0.height() + 1,
+            xmin: grid.x_min(),
+            xmax: grid.0.width() - 1,
+        });
+        if let Some(floor) = &floor {
+            grid.add_line(Pos::new(floor.xmin, floor.y), Pos::new(floor.xmax, floor.y));
+        }
+        Pouring {
+            grid,
+            floor,
+            count: 0,
+            last: None,
+            done: false,
+        }
+    }
+
+    /// Pours one unit of sand until it comes to rest. Returns false once sand falls
+    /// into the void, or after the source is blocked.
+    fn pour(&mut self) -> bool {
+        while !self.done {
+            match (self.grid.simulate_step(), &mut self.floor) {
+                (None, None) => self.done = true,
+                (None, Some(floor)) => {
+                    floor.xmax += 1;
+                    floor.xmin = floor.xmin.saturating_sub(1);
+                    self.grid.add_point(floor.xmax, floor.y);
+                    self.grid.add_point(floor.xmin, floor.y);
+                }
+                (Some(pos), _) => {
+                    self.count += 1;
+                    self.last = Some(pos);
+                    *self.grid.at_mut(pos.x, pos.y).unwrap() = Point::SandRest;
+                    self.done = pos == SOURCE;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl Animation for Pouring {
+    fn frame(&self) -> String {
+        format!("{}{} units of sand at rest\n", self.grid, self.count)
+    }
+
+    fn step(&mut self) -> bool {
+        self.pour()
+    }
+}
+
+impl Session for Pouring {
+    fn unit(&self) -> &'static str {
+        "sand"
+    }
+
+    fn step(&mut self) -> Result<bool> {
+        Ok(self.pour())
+    }
+
+    fn show(&self) -> String {
+        self.frame()
+    }
+
+    /// The units of sand at rest, and where the last one came to rest.
+    fn values(&self) -> Vec<(String, i64)> {
+        let (x, y) = self.last.map_or((-1, -1), |p| (p.x as i64, p.y as i64));
+        vec![
+            ("sand".to_owned(), self.count as i64),
+            ("x".to_owned(), x),
+            ("y".to_owned(), y),
+        ]
+    }
+}
+
+impl Debugged for Day14 {
+    fn session(g: &Self::Input, part: Part, _params: &Params) -> Result<Box<dyn Session>> {
+        Ok(Box::new(Pouring::new(g.clone(), part == Part::Two)))
+    }
+}
+
+/// Returns the number of sand units that come to rest before sand falls into the void.
+/// Fails if cancelled.
+pub fn simulate(g: Grid, cancel: &Cancel) -> Result<usize, Cancelled> {
+    trace!("initial grid:\n{g}");
+    let mut sand = Pouring::new(g, false);
+    while sand.pour() {
+        cancel.check()?;
+    }
+    trace!("final grid:\n{}", sand.grid);
+    Ok(sand.count)
+}
+
+/// Returns the number of sand units that come to rest until the source is blocked.
+/// Fails if cancelled.
+pub fn simulate_finite(g: Grid, cancel: &Cancel) -> Result<usize, Cancelled> {
+    let mut sand = Pouring::new(g, true);
+    trace!("initial grid:\n{}", sand.grid);
+    while sand.pour() {
+        cancel.check()?;
+    }
+    trace!("final grid:\n{}", sand.grid);
+    Ok(sand.count)
+}
+
+fn build_grid(input: &str) -> Result<Grid, ParseError> {
+    let paths = parse::lines(Day14::DAY, input)
+        .map(|ll| ll.text().split(" -> ").map(|p| ll.parse(p)).collect())
+        .collect::<Result<Vec<Vec<Pos>>, _>>()?;
+
+    let mut g = Grid::new();
+    // size the grid once, instead of growing it with every line
+    if let Some(bbox) = BoundingBox::of(paths.iter().flatten().copied()) {
+        g.extend_to(bbox.max.x, bbox.max.y);
+    }
+
+    for path in &paths {
+        for (last, next) in path.iter().tuple_windows() {
+            g.add_line(*last, *next);
+        }
+    }
+    Ok(g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    #[test]
+    fn snapshot_sand() {
+        let grid = Day14::parse(&crate::example(14).unwrap()).unwrap();
+        assert_snapshot("day14_rocks", &grid.to_string());
+        for (name, with_floor) in [("day14_void", false), ("day14_floor", true)] {
+            let mut sand = Pouring::new(grid.clone(), with_floor);
+            while sand.pour() {}
+            assert_snapshot(name, &sand.grid.to_string());
+        }
+    }
+}
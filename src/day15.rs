@@ -0,0 +1,182 @@
+//! Day 15: Beacon Exclusion Zone.
+
+use anyhow::{Context, Result};
+use std::{collections::HashSet, fmt};
+
+use crate::{
+    cancel::{Cancel, Cancelled},
+    differential::Reference,
+    geom::Point2,
+    parse::{self, Line},
+    Params, ParseError, Solution,
+};
+
+pub type Coord = Point2<i32>;
+
+/// The largest coordinate accepted: far beyond the puzzle's, yet small enough for the
+/// distances and the covered ranges to fit into an `i32`.
+const MAX_COORD: i32 = 1 << 28;
+
+/// `x * 4000000 + y`
+pub fn tuning_frequency(beacon: Coord) -> Result<usize> {
+    let x_big: usize = TryInto::<usize>::try_into(beacon.x)? * 4000000;
+    Ok(x_big + TryInto::<usize>::try_into(beacon.y)?)
+}
+
+/// Parses `x=2, y=18`.
+fn parse_coord(ll: &Line, s: &str) -> Result<Coord, ParseError> {
+    let (x, y) = ll.split_once(s, ", ")?;
+    let (_, x) = ll.split_once(x, "=")?;
+    let (_, y) = ll.split_once(y, "=")?;
+    let coord = |s| match ll.parse::<i32>(s)? {
+        c if (-MAX_COORD..=MAX_COORD).contains(&c) => Ok(c),
+        _ => Err(ll.error(s, format!("expected a coordinate within ±{MAX_COORD}"))),
+    };
+    Ok(Coord::new(coord(x)?, coord(y)?))
+}
+
+/// A sensor, and the beacon closest to it.
+pub struct Sensor {
+    position: Coord,
+    nearest: Coord,
+}
+
+impl Sensor {
+    pub fn new(position: Coord, nearest: Coord) -> Self {
+        Sensor { position, nearest }
+    }
+
+    pub fn position(&self) -> Coord {
+        self.position
+    }
+
+    /// The position of the closest beacon.
+    pub fn nearest(&self) -> Coord {
+        self.nearest
+    }
+
+    /// Manhattan distance to the nearest beacon
+    pub fn range(&self) -> u32 {
+        self.position.manhattan(self.nearest)
+    }
+
+    /// Returns the first and the last x coordinate covered in the given line.
+    pub fn covered_bounds(&self, line: i32) -> Option<(i32, i32)> {
+        let dist = line.abs_diff(self.position.y);
+        if self.range() >= dist {
+            let width: i32 = (self.range() - dist).try_into().unwrap();
+            let min = self.position.x - width;
+            let max = self.position.x + width;
+            return Some((min, max));
+        }
+        None
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = Vec<Sensor>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(sensors: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        Ok(covered_in_line(sensors, params.get("part1_row")?))
+    }
+
+    fn part2(sensors: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let upper = params.get("part2_bound")?;
+        let beacon =
+            find_uncovered(sensors, upper, params.cancel())?.context("beacon not found")?;
+        tuning_frequency(beacon)
+    }
+}
+
+impl Reference for Day15 {
+    /// Checks every position of the line against every sensor.
+    fn reference1(sensors: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let line = params.get("part1_row")?;
+        let reach = |s: &Sensor| i32::try_from(s.range()).unwrap_or(i32::MAX);
+        let min = sensors.iter().map(|s| s.position.x - reach(s)).min();
+        let max = sensors.iter().map(|s| s.position.x + reach(s)).max();
+        let (Some(min), Some(max)) = (min, max) else {
+            return Ok(0);
+        };
+        let covered = (min..=max)
+            .map(|x| Coord::new(x, line))
+            .filter(|&p| sensors.iter().all(|s| s.nearest != p))
+            .filter(|&p| sensors.iter().any(|s| s.position.manhattan(p) <= s.range()))
+            .count();
+        Ok(covered)
+    }
+
+    /// Checks every position of the search area against every sensor.
+    fn reference2(sensors: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let upper: i32 = params.get("part2_bound")?;
+        let beacon = (0..=upper)
+            .flat_map(|y| (0..=upper).map(move |x| Coord::new(x, y)))
+            .find(|&p| sensors.iter().all(|s| s.position.manhattan(p) > s.range()))
+            .context("beacon not found")?;
+        tuning_frequency(beacon)
+    }
+}
+
+/// The only position from (0, 0) to (`upper`, `upper`) no sensor covers.
+/// Fails if cancelled.
+pub fn find_uncovered(
+    sensors: &[Sensor],
+    upper: i32,
+    cancel: &Cancel,
+) -> Result<Option<Coord>, Cancelled> {
+    for line in 0..upper + 1 {
+        // checking on every line slows the search down by about a tenth
+        if line % 4096 == 0 {
+            cancel.check()?;
+        }
+        let mut lowest_uncovered = 0;
+        for s in sensors {
+            if let Some((min, max)) = s.covered_bounds(line) {
+                if min <= lowest_uncovered && lowest_uncovered <= max {
+                    lowest_uncovered = max + 1;
+                }
+            }
+        }
+
+        if lowest_uncovered <= upper {
+            return Ok(Some(Coord::new(lowest_uncovered, line)));
+        }
+    }
+    Ok(None)
+}
+
+/// The number of positions in the line where there cannot be a beacon.
+pub fn covered_in_line(sensors: &[Sensor], line: i32) -> usize {
+    let mut covered = HashSet::new();
+    for sensor in sensors {
+        if let Some((min, max)) = sensor.covered_bounds(line) {
+            covered.extend(min..max + 1);
+        }
+    }
+    for sensor in sensors {
+        if sensor.nearest.y == line {
+            covered.remove(&sensor.nearest.x);
+        }
+    }
+    covered.len()
+}
+
+fn parse(example: &str) -> Result<Vec<Sensor>, ParseError> {
+    let mut sensors = Vec::new();
+    for ll in parse::lines(Day15::DAY, example) {
+        let (pos, beacon) = ll.split_once(ll.text(), ":")?;
+        let (_, pos) = ll.split_once(pos, "at ")?;
+        let (_, beacon) = ll.split_once(beacon, "at ")?;
+        let (pos, beacon) = (parse_coord(&ll, pos)?, parse_coord(&ll, beacon)?);
+        sensors.push(Sensor::new(pos, beacon));
+    }
+    sensors.sort_by_key(|s| s.position.x);
+    Ok(sensors)
+}
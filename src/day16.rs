@@ -0,0 +1,685 @@
+//! Day 16: Proboscidea Volcanium.
+
+use anyhow::{ensure, Context, Result};
+use itertools::Itertools;
+use log::debug;
+use std::hash::Hash;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    io::Write,
+    rc::Rc,
+    str::FromStr,
+};
+
+use crate::{
+    cancel::{Cancel, Cancelled},
+    differential::Reference,
+    parse, Params, ParseError, Solution,
+};
+
+/// A valve's two-letter label.
+#[derive(Debug, Clone, Eq)]
+pub struct ValveId {
+    id: Option<usize>,
+    label: [u8; 2],
+}
+
+impl ValveId {
+    fn numeric(&self) -> usize {
+        self.id
+            .expect("The numeric ID is guaranteed to exist by Network::build")
+    }
+}
+
+impl FromStr for ValveId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let label: [u8; 2] = s
+            .as_bytes()
+            .try_into()
+            .ok()
+            .filter(|l: &[u8; 2]| l.iter().all(u8::is_ascii_uppercase))
+            .with_context(|| format!("invalid valve label: {s}"))?;
+        Ok(ValveId { id: None, label })
+    }
+}
+
+impl PartialEq for ValveId {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+    }
+}
+
+impl Hash for ValveId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.label.hash(state)
+    }
+}
+
+impl fmt::Display for ValveId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.label))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Valve {
+    id: ValveId,
+    neighbours: Vec<ValveId>,
+    rate: u32,
+}
+
+impl Valve {
+    pub fn label(&self) -> &ValveId {
+        &self.id
+    }
+
+    /// The flow rate, in pressure per minute once opened.
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// The valves reachable through a tunnel in one minute.
+    pub fn neighbours(&self) -> &[ValveId] {
+        &self.neighbours
+    }
+}
+
+impl FromStr for Valve {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, last) = s.split_once(';').context("expected ';' in the middle")?;
+        let mut first = first.split_ascii_whitespace();
+        let id = first.nth(1).context("id not found")?.parse()?;
+        let rate = first
+            .last()
+            .context("rate not found")?
+            .split_once('=')
+            .context("expected '=' in rate")?
+            .1
+            .parse()?;
+        let neighbours = last
+            .split_ascii_whitespace()
+            .skip(4)
+            .map(|n| n.trim_end_matches(',').parse())
+            .collect::<Result<Vec<ValveId>, _>>()?;
+        Ok(Valve {
+            id,
+            neighbours,
+            rate,
+        })
+    }
+}
+
+/// The valves and the tunnels between them.
+#[derive(Clone)]
+pub struct Network {
+    nodes: Vec<Valve>,
+}
+
+impl Network {
+    fn build(input: &str) -> Result<Self, ParseError> {
+        let mut valves_map = HashMap::new();
+        let lines: Vec<_> = parse::lines(Day16::DAY, input).collect();
+        // the solver keeps the opened valves as bits of a u128
+        if let Some(ll) = lines.get(128) {
+            return Err(ll.error(ll.text(), "expected at most 128 valves"));
+        }
+        for ll in &lines {
+            let v: Valve = ll.parse(ll.text())?;
+            valves_map.insert(v.id.clone(), v);
+        }
+        for ll in &lines {
+            let (_, tunnels) = ll.split_once(ll.text(), ";")?;
+            for n in tunnels.split_ascii_whitespace().skip(4) {
+                let n = n.trim_end_matches(',');
+                if !valves_map.keys().any(|id| id.label == n.as_bytes()) {
+                    return Err(ll.error(n, "unknown valve"));
+                }
+            }
+        }
+        // the solvers start at AA, the first valve once sorted
+        if !valves_map.keys().any(|id| &id.label == b"AA") {
+            return Err(ParseError::end_of_input(Day16::DAY, input, "no valve AA"));
+        }
+
+        let mut valves_vec = Vec::new();
+        // sort labels, resolve IDs
+        for (i, (_, v)) in valves_map
+            .iter_mut()
+            .sorted_by_key(|(k, _)| k.label)
+            .enumerate()
+        {
+            v.id.id = Some(i);
+            valves_vec.push(v.clone());
+        }
+
+        // convert edges
+        for v in &mut valves_vec {
+            for n in &mut v.neighbours {
+                let id = valves_map[n].id.numeric();
+                n.id = Some(id);
+            }
+        }
+
+        Ok(Self { nodes: valves_vec })
+    }
+
+    fn node(&self, id: usize) -> Option<&Valve> {
+        self.nodes.get(id)
+    }
+
+    /// The valves, ordered by label.
+    pub fn valves(&self) -> impl Iterator<Item = &Valve> {
+        self.nodes.iter()
+    }
+
+    /// write the graph to disk in Trivial Graph Format for debugging
+    #[allow(dead_code)]
+    fn write_tgf(&self, name: &str) -> std::io::Result<()> {
+        let mut f = std::fs::File::create(name.to_owned() + ".tgf")?;
+
+        // list of nodes first
+        for v in &self.nodes {
+            writeln!(f, "{} {},{}", v.id.numeric(), v.id, v.rate)?;
+        }
+        // hashtag separator
+        writeln!(f, "#")?;
+        // list of edges
+        for v in &self.nodes {
+            for n in &v.neighbours {
+                writeln!(f, "{} {}", v.id.numeric(), n.id.unwrap())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Action {
+    MoveTo(ValveId),
+    Open(ValveId),
+    Wait,
+}
+
+/// The last action of a path, linked to the ones before: the paths share their
+/// common beginnings.
+#[derive(Debug)]
+struct Step {
+    action: Action,
+    before: Option<Rc<Step>>,
+}
+
+#[derive(Clone, Debug)]
+struct PathState {
+    last: Option<Rc<Step>>,
+    minutes: u32,
+    /// The opened valves, by numeric ID
+    open: u128,
+    /// The pressure released so far
+    released: u32,
+    /// The pressure released per minute
+    rate: u32,
+}
+
+impl PathState {
+    #[must_use]
+    fn new() -> Self {
+        Self::new_elephant(0)
+    }
+
+    /// A path that starts after teaching the elephant for the given minutes.
+    #[must_use]
+    fn new_elephant(teaching: u32) -> Self {
+        let start = PathState {
+            last: None,
+            minutes: 0,
+            open: 0,
+            released: 0,
+            rate: 0,
+        };
+        (0..teaching).fold(start, |path, _| path.with_wait())
+    }
+
+    #[must_use]
+    fn with(&self, action: Action) -> Self {
+        let before = self.last.clone();
+        Self {
+            last: Some(Rc::new(Step { action, before })),
+            minutes: self.minutes + 1,
+            open: self.open,
+            released: self.released + self.rate,
+            rate: self.rate,
+        }
+    }
+
+    #[must_use]
+    fn with_move(&self, v_id: &ValveId) -> Self {
+        self.with(Action::MoveTo(v_id.clone()))
+    }
+
+    #[must_use]
+    fn with_open(&self, v: &Valve) -> Self {
+        assert!(!self.is_open(v.id.numeric()), "cannot open an opened valve");
+
+        let mut path = self.with(Action::Open(v.id.clone()));
+        path.open |= 1 << v.id.numeric();
+        path.rate += v.rate;
+        path
+    }
+
+    #[must_use]
+    fn with_wait(&self) -> Self {
+        self.with(Action::Wait)
+    }
+
+    fn is_open(&self, num: usize) -> bool {
+        self.open & (1 << num) != 0
+    }
+
+    /// The actions, from the first minute on.
+    fn actions(&self) -> Vec<&Action> {
+        let mut actions = Vec::new();
+        let mut step = self.last.as_deref();
+        while let Some(Step { action, before }) = step {
+            actions.push(action);
+            step = before.as_deref();
+        }
+        actions.reverse();
+        actions
+    }
+
+    fn opened(&self) -> impl Iterator<Item = &ValveId> {
+        self.actions().into_iter().filter_map(|a| match a {
+            Action::Open(vid) => Some(vid),
+            _ => None,
+        })
+    }
+
+    fn minutes(&self) -> u32 {
+        self.minutes
+    }
+
+    fn total_flow(&self) -> u32 {
+        self.released
+    }
+}
+
+/// The best path to a valve for each set of opened valves: the paths opening the
+/// same valves have the same options left, so only the best of them matters.
+type ValveState = BTreeMap<u128, PathState>;
+
+impl fmt::Display for PathState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "open: ")?;
+        for open in self.opened() {
+            write!(f, "{} ", open)?;
+        }
+
+        write!(f, "\thistory: ")?;
+        let start_v = "AA".parse().unwrap();
+        let mut last_v = &start_v;
+        for act in self.actions() {
+            if let Action::MoveTo(vid) = act {
+                last_v = vid;
+            }
+            write!(f, "{} ", last_v)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The paths at valve AA, before the first minute.
+fn start_state(g: &Network, path: PathState) -> Vec<ValveState> {
+    let mut state = vec![ValveState::new(); g.valves().count()];
+    state[0].insert(0, path);
+    state
+}
+
+/// The most pressure to release in the given minutes with the elephant's help,
+/// after spending `teaching` of them teaching it. Fails if cancelled.
+pub fn find_path_elephant(
+    g: &Network,
+    minutes: u32,
+    teaching: u32,
+    cancel: &Cancel,
+) -> Result<u32, Cancelled> {
+    let mut last_state = start_state(g, PathState::new_elephant(teaching));
+    for min in teaching..minutes {
+        cancel.check()?;
+        simulate_step(g, &mut last_state, min);
+    }
+
+    // the best path for each set of opened valves, whichever valve it ends at
+    let mut best = BTreeMap::<u128, &PathState>::new();
+    for path in last_state.iter().flat_map(ValveState::values) {
+        let entry = best.entry(path.open).or_insert(path);
+        if path.total_flow() > entry.total_flow() {
+            *entry = path;
+        }
+    }
+    let mut best: Vec<&PathState> = best.into_values().collect();
+    best.sort_by_key(|path| std::cmp::Reverse(path.total_flow()));
+
+    // the human and the elephant open different valves
+    let mut max_flow = 0;
+    let (mut human_best, mut elephant_best) = (best[0], best[0]);
+    for (i, human) in best.iter().enumerate() {
+        cancel.check()?;
+        for elephant in &best[i..] {
+            let flow = human.total_flow() + elephant.total_flow();
+            if flow <= max_flow {
+                // the paths are sorted, none of the others is better
+                break;
+            }
+            if human.open & elephant.open == 0 {
+                (human_best, elephant_best) = (human, elephant);
+                max_flow = flow;
+            }
+        }
+    }
+
+    debug!("best      {human_best}");
+    debug!("elephant  {elephant_best}");
+    Ok(max_flow)
+}
+
+/// The most pressure to release alone in the given minutes. Fails if cancelled.
+pub fn find_path_solo(g: &Network, minutes: u32, cancel: &Cancel) -> Result<u32, Cancelled> {
+    let best = best_path_solo(g, minutes, cancel)?;
+    debug!("{best}");
+    Ok(best.total_flow())
+}
+
+fn best_path_solo(g: &Network, minutes: u32, cancel: &Cancel) -> Result<PathState, Cancelled> {
+    let mut last_state = start_state(g, PathState::new());
+    for min in 0..minutes {
+        cancel.check()?;
+        simulate_step(g, &mut last_state, min);
+    }
+
+    let mut best = &last_state[0][&0];
+    for path in last_state.iter().flat_map(ValveState::values) {
+        if path.total_flow() > best.total_flow() {
+            best = path;
+        }
+    }
+    Ok(best.clone())
+}
+
+/// Advances the best paths to each valve by the minute `min`.
+fn simulate_step(g: &Network, last_state: &mut Vec<ValveState>, min: u32) {
+    let mut cur_state = vec![ValveState::new(); last_state.len()];
+
+    for (v_id, v_state) in last_state.iter().enumerate() {
+        let v = g.node(v_id).unwrap();
+        for path in v_state.values() {
+            debug_assert_eq!(path.minutes(), min);
+            // whatever happens in this minute, the open valves release their pressure
+            let released = path.total_flow() + path.rate;
+
+            // open the valve...
+            if !path.is_open(v_id) && v.rate > 0 {
+                let open = path.open | 1 << v_id;
+                keep_best(&mut cur_state[v_id], open, released, || path.with_open(v));
+            }
+            // ...or wait...
+            keep_best(&mut cur_state[v_id], path.open, released, || {
+                path.with_wait()
+            });
+            // ...or move to a neighbour
+            for n_id in &v.neighbours {
+                keep_best(&mut cur_state[n_id.numeric()], path.open, released, || {
+                    path.with_move(n_id)
+                });
+            }
+        }
+    }
+
+    *last_state = cur_state;
+}
+
+/// Keeps the path (only built if needed) if it has released more pressure than the
+/// path to the valve opening the same valves: from here on, they release the same.
+fn keep_best(
+    v_state: &mut ValveState,
+    open: u128,
+    released: u32,
+    path: impl FnOnce() -> PathState,
+) {
+    match v_state.get(&open) {
+        Some(known) if known.total_flow() >= released => {}
+        _ => {
+            v_state.insert(open, path());
+        }
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Network;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Network::build(input)
+    }
+
+    fn part1(g: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        Ok(find_path_solo(g, params.get("minutes")?, params.cancel())?)
+    }
+
+    fn part2(g: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let (minutes, teaching) = (params.get("minutes")?, params.get("teaching_minutes")?);
+        ensure!(
+            teaching <= minutes,
+            "no time left after teaching the elephant"
+        );
+        Ok(find_path_elephant(g, minutes, teaching, params.cancel())?)
+    }
+}
+
+impl Reference for Day16 {
+    fn reference1(g: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let best = best_per_opened_set(g, params.get("minutes")?)?;
+        Ok(best.into_values().max().unwrap_or(0))
+    }
+
+    /// Splits the valves between you and the elephant in every possible way.
+    fn reference2(g: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        let minutes: u32 = params.get("minutes")?;
+        let teaching: u32 = params.get("teaching_minutes")?;
+        let best = best_per_opened_set(g, minutes.saturating_sub(teaching))?;
+        let mut max = 0;
+        for (&mine, &flow) in &best {
+            for (&elephants, &other) in &best {
+                if mine & elephants == 0 {
+                    max = max.max(flow + other);
+                }
+            }
+        }
+        Ok(max)
+    }
+}
+
+/// Walks every order of opening the working valves, starting at AA, and returns
+/// the most pressure released for each set of opened valves (as a bit mask).
+fn best_per_opened_set(g: &Network, minutes: u32) -> Result<HashMap<u64, u32>> {
+    let start = g
+        .valves()
+        .position(|v| &v.id.label == b"AA")
+        .context("no valve AA")?;
+    let working: Vec<usize> = (0..g.nodes.len())
+        .filter(|&i| g.nodes[i].rate > 0)
+        .collect();
+    anyhow::ensure!(working.len() <= 64, "too many working valves");
+
+    // breadth-first search from every valve
+    let distances: Vec<Vec<Option<u32>>> = (0..g.nodes.len())
+        .map(|from| {
+            let mut dist = vec![None; g.nodes.len()];
+            dist[from] = Some(0);
+            let mut queue = std::collections::VecDeque::from([from]);
+            while let Some(cur) = queue.pop_front() {
+                for n in &g.nodes[cur].neighbours {
+                    if dist[n.numeric()].is_none() {
+                        dist[n.numeric()] = Some(dist[cur].unwrap() + 1);
+                        queue.push_back(n.numeric());
+                    }
+                }
+            }
+            dist
+        })
+        .collect();
+
+    fn visit(
+        g: &Network,
+        working: &[usize],
+        distances: &[Vec<Option<u32>>],
+        (at, left, opened, flow): (usize, u32, u64, u32),
+        best: &mut HashMap<u64, u32>,
+    ) {
+        let entry = best.entry(opened).or_default();
+        *entry = (*entry).max(flow);
+        for (bit, &valve) in working.iter().enumerate() {
+            if opened & (1 << bit) != 0 {
+                continue;
+            }
+            // walk there, then one minute to open it
+            let Some(cost) = distances[at][valve].map(|d| d + 1) else {
+                continue;
+            };
+            if cost < left {
+                let left = left - cost;
+                let state = (
+                    valve,
+                    left,
+                    opened | (1 << bit),
+                    flow + left * g.nodes[valve].rate,
+                );
+                visit(g, working, distances, state, best);
+            }
+        }
+    }
+
+    let mut best = HashMap::new();
+    visit(g, &working, &distances, (start, minutes, 0, 0), &mut best);
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    #[test]
+    fn part1_example() {
+        let example = crate::example(16).unwrap();
+        let g = Network::build(&example).unwrap();
+        assert_eq!(find_path_solo(&g, 30, &Cancel::default()).unwrap(), 1651);
+    }
+
+    #[test]
+    fn part1_input() {
+        let input = crate::input(16).unwrap();
+        let g = Network::build(&input).unwrap();
+        assert_eq!(find_path_solo(&g, 30, &Cancel::default()).unwrap(), 1871);
+    }
+
+    #[test]
+    fn part2_example() {
+        let example = crate::example(16).unwrap();
+        let g = Network::build(&example).unwrap();
+        assert_eq!(
+            find_path_elephant(&g, 30, 4, &Cancel::default()).unwrap(),
+            1707
+        );
+    }
+
+    #[test]
+    fn part2_input() {
+        let input = crate::input(16).unwrap();
+        let g = Network::build(&input).unwrap();
+        assert_eq!(
+            find_path_elephant(&g, 30, 4, &Cancel::default()).unwrap(),
+            2416
+        );
+    }
+
+    #[test]
+    fn open_the_near_valve_first() {
+        // found by the differential tests: the first valve to open is worth less, but
+        // opening it first wastes less time (388, not 377)
+        let g = Network::build(
+            "Valve LQ has flow rate=7; tunnel leads to valve AA
+Valve FC has flow rate=0; tunnel leads to valve HN
+Valve WX has flow rate=8; tunnel leads to valve HN
+Valve HN has flow rate=0; tunnels lead to valves FC, WX, AA
+Valve AA has flow rate=0; tunnels lead to valves HN, LQ
+",
+        )
+        .unwrap();
+        assert_eq!(find_path_solo(&g, 30, &Cancel::default()).unwrap(), 388);
+    }
+
+    #[test]
+    fn snapshot_path() {
+        let g = Network::build(&crate::example(16).unwrap()).unwrap();
+        assert_snapshot(
+            "day16_path",
+            &best_path_solo(&g, 30, &Cancel::default())
+                .unwrap()
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn start_at_aa() {
+        let err = Network::build("Valve BB has flow rate=3; tunnel leads to valve BB\n")
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.message.as_str()), (1, "no valve AA"));
+        assert!(Network::build("").is_err());
+    }
+
+    #[test]
+    fn valveid_equality() {
+        let mut v1: ValveId = "AA".parse().unwrap();
+        v1.id = Some(42);
+        let mut v2: ValveId = "AA".parse().unwrap();
+        v2.id = Some(21);
+        assert_eq!(v1, v2);
+
+        let v3: ValveId = "AA".parse().unwrap();
+        assert_eq!(v1, v3);
+        let v4: ValveId = "XX".parse().unwrap();
+        assert_ne!(v1, v4);
+    }
+
+    #[test]
+    fn valveid_hash_equality() {
+        fn hash(v: &ValveId) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            v.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut v1: ValveId = "AA".parse().unwrap();
+        v1.id = Some(42);
+        let mut v2: ValveId = "AA".parse().unwrap();
+        v2.id = Some(21);
+        assert_eq!(hash(&v1), hash(&v2));
+
+        let v3: ValveId = "AA".parse().unwrap();
+        assert_eq!(hash(&v1), hash(&v3));
+        let v4: ValveId = "XX".parse().unwrap();
+        assert_ne!(hash(&v1), hash(&v4));
+    }
+}
@@ -0,0 +1,113 @@
+//! Day 2: Rock Paper Scissors.
+
+use std::fmt::Display;
+
+use anyhow::Result;
+
+use crate::{
+    parse::{self, Line},
+    Params, ParseError, Solution,
+};
+
+/// A shape, whose value is its score.
+#[derive(Debug, Copy, Clone)]
+pub enum Move {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+/// The score of one round of (opponent, me) moves.
+pub fn score(moves: &(Move, Move)) -> i32 {
+    use Move::{Paper, Rock, Scissors};
+    let score = match moves {
+        (Rock, Rock) | (Paper, Paper) | (Scissors, Scissors) => 3, /* draw */
+        (Rock, Paper) | (Paper, Scissors) | (Scissors, Rock) => 6, /* win  */
+        (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper) => 0, /* loss */
+    };
+    score + moves.1 as i32
+}
+
+/// The total score of following the guide.
+pub fn total_score(guide: &[(Move, Move)]) -> i32 {
+    guide.iter().fold(0, |acc, moves| acc + score(moves))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    /// The strategy guide read as (opponent, me) moves, and read as (opponent, outcome).
+    type Input = (Vec<(Move, Move)>, Vec<(Move, Move)>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((make_guide(input)?, make_guide2(input)?))
+    }
+
+    fn part1((guide, _): &Self::Input, _params: &Params) -> Result<impl Display> {
+        Ok(total_score(guide))
+    }
+
+    fn part2((_, guide2): &Self::Input, _params: &Params) -> Result<impl Display> {
+        Ok(total_score(guide2))
+    }
+}
+
+/// Splits a line of the guide into the opponent's move and the second column.
+fn read_line<'a>(ll: &Line<'a>) -> Result<(Move, &'a str), ParseError> {
+    use Move::{Paper, Rock, Scissors};
+    let (opp, me) = ll.split_once(ll.text(), " ")?;
+    let opp_move = match opp {
+        "A" => Rock,
+        "B" => Paper,
+        "C" => Scissors,
+        _ => return Err(ll.error(opp, "expected A, B or C")),
+    };
+    Ok((opp_move, me))
+}
+
+fn make_guide(input: &str) -> Result<Vec<(Move, Move)>, ParseError> {
+    use Move::{Paper, Rock, Scissors};
+    let mut guide = Vec::<(Move, Move)>::new();
+
+    for ll in parse::lines(Day2::DAY, input) {
+        let (opp_move, me) = read_line(&ll)?;
+        let my_move = match me {
+            "X" => Rock,
+            "Y" => Paper,
+            "Z" => Scissors,
+            _ => return Err(ll.error(me, "expected X, Y or Z")),
+        };
+
+        guide.push((opp_move, my_move));
+    }
+    Ok(guide)
+}
+
+fn make_guide2(input: &str) -> Result<Vec<(Move, Move)>, ParseError> {
+    use Move::{Paper, Rock, Scissors};
+    let mut guide = Vec::<(Move, Move)>::new();
+
+    for ll in parse::lines(Day2::DAY, input) {
+        let (opp_move, me) = read_line(&ll)?;
+        let my_move = match me {
+            "X" => match opp_move {
+                // need to lose
+                Rock => Scissors,
+                Paper => Rock,
+                Scissors => Paper,
+            },
+            "Y" => opp_move, // need draw
+            "Z" => match opp_move {
+                // need to win
+                Rock => Paper,
+                Paper => Scissors,
+                Scissors => Rock,
+            },
+            _ => return Err(ll.error(me, "expected X, Y or Z")),
+        };
+
+        guide.push((opp_move, my_move));
+    }
+    Ok(guide)
+}
@@ -0,0 +1,164 @@
+//! Day 3: Rucksack Reorganization.
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use std::fmt::Display;
+
+use crate::{
+    parse::{self, Line},
+    Params, ParseError, Solution,
+};
+
+/// An item type, by index: `a`-`z` are 0-25, `A`-`Z` are 26-51.
+pub struct Item(u8);
+
+impl Item {
+    /// The item type of a letter.
+    pub fn from_ascii(chr: u8) -> Option<Self> {
+        match chr {
+            b'a'..=b'z' => Some(Self(chr - b'a')),
+            b'A'..=b'Z' => Some(Self(chr - b'A' + 26)),
+            _ => None,
+        }
+    }
+
+    pub fn to_ascii(&self) -> char {
+        if self.0 < 26 {
+            (b'a' + self.0).into()
+        } else {
+            (b'A' + self.0 - 26).into()
+        }
+    }
+
+    fn idx(&self) -> usize {
+        self.0.into()
+    }
+
+    /// 1 to 26 for `a` to `z`, 27 to 52 for `A` to `Z`.
+    pub fn priority(&self) -> i32 {
+        (self.0 + 1).into()
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} ({})", self.priority(), self.to_ascii()))
+    }
+}
+
+/// How often each item type is in the two compartments of a rucksack.
+pub struct Rucksack {
+    first: [i32; 52],
+    second: [i32; 52],
+}
+
+impl Rucksack {
+    fn new(ll: &Line) -> Result<Self, ParseError> {
+        let items = ll.text();
+        // the compartments are split by byte, which needs one byte per item
+        if let Some((i, chr)) = items.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(ll.error(&items[i..i + chr.len_utf8()], "expected a letter"));
+        }
+        if !items.len().is_multiple_of(2) {
+            return Err(ll.error(items, "expected an even number of items"));
+        }
+        let count = |half: &str| {
+            let mut acc = [0; 52];
+            for (i, chr) in half.bytes().enumerate() {
+                let item = Item::from_ascii(chr)
+                    .ok_or_else(|| ll.error(&half[i..i + 1], "expected a letter"))?;
+                acc[item.idx()] += 1;
+            }
+            Ok(acc)
+        };
+        let (first, second) = items.split_at(items.len() / 2);
+        Ok(Self {
+            first: count(first)?,
+            second: count(second)?,
+        })
+    }
+
+    /// The item type in both compartments.
+    pub fn find_dup(&self) -> Option<Item> {
+        (0..52)
+            .find(|&i| self.first[usize::from(i)] != 0 && self.second[usize::from(i)] != 0)
+            .map(Item)
+    }
+
+    fn at(&self, idx: usize) -> i32 {
+        self.first[idx] + self.second[idx]
+    }
+}
+
+/// The item type in all three rucksacks.
+pub fn find_badge(rs1: &Rucksack, rs2: &Rucksack, rs3: &Rucksack) -> Option<Item> {
+    (0..52)
+        .find(|&i| {
+            let i = usize::from(i);
+            rs1.at(i) != 0 && rs2.at(i) != 0 && rs3.at(i) != 0
+        })
+        .map(Item)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<Rucksack>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(Self::DAY, input)
+            .map(|ll| Rucksack::new(&ll))
+            .collect()
+    }
+
+    fn part1(rucksacks: &Self::Input, _params: &Params) -> Result<impl Display> {
+        Ok(dups(rucksacks)?.iter().map(Item::priority).sum::<i32>())
+    }
+
+    fn part2(rucksacks: &Self::Input, _params: &Params) -> Result<impl Display> {
+        Ok(badges(rucksacks)?.iter().map(Item::priority).sum::<i32>())
+    }
+}
+
+/// The item type in both compartments, for every rucksack.
+pub fn dups(rucksacks: &[Rucksack]) -> Result<Vec<Item>> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(i, rs)| {
+            rs.find_dup()
+                .with_context(|| format!("no duplicate item in rucksack {}", i + 1))
+        })
+        .collect()
+}
+
+/// The item type common to each group of three rucksacks.
+pub fn badges(rucksacks: &[Rucksack]) -> Result<Vec<Item>> {
+    let mut badges = Vec::<Item>::new();
+    for (i, group) in rucksacks.iter().chunks(3).into_iter().enumerate() {
+        let (rs1, rs2, rs3) = group
+            .collect_tuple()
+            .context("the number of rucksacks is not a multiple of 3")?;
+        let badge = find_badge(rs1, rs2, rs3)
+            .with_context(|| format!("no common item in group {}", i + 1))?;
+        badges.push(badge);
+    }
+    Ok(badges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors() {
+        let err = Day3::parse("abAB\nab\u{e9}b\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.width), (2, 3, 1));
+        assert_eq!(err.message, "expected a letter");
+        let err = Day3::parse("ab1b\n").err().unwrap();
+        assert_eq!((err.column, err.message.as_str()), (3, "expected a letter"));
+        let err = Day3::parse("abc\n").err().unwrap();
+        assert_eq!(err.message, "expected an even number of items");
+    }
+}
@@ -0,0 +1,77 @@
+//! Day 4: Camp Cleanup.
+
+use std::fmt::Display;
+
+use anyhow::Result;
+
+use crate::{
+    parse::{self, Line},
+    Params, ParseError, Solution,
+};
+
+/// The sections assigned to an elf, from `lower` to `upper` (inclusive).
+pub struct Work {
+    lower: u32,
+    upper: u32,
+}
+
+impl Work {
+    fn parse(ll: &Line, s_range: &str) -> Result<Self, ParseError> {
+        let (l, r) = ll.split_once(s_range, "-")?;
+        let lower = ll.parse(l)?;
+        let upper = ll.parse(r)?;
+        Ok(Self { lower, upper })
+    }
+
+    pub fn new(lower: u32, upper: u32) -> Self {
+        Self { lower, upper }
+    }
+
+    /// Whether all of `other`'s sections are also ours.
+    pub fn contains(&self, other: &Work) -> bool {
+        if self.lower <= other.lower && self.upper >= other.upper {
+            return true;
+        }
+        false
+    }
+
+    /// Whether `other`'s first section is also ours (check both ways for any overlap).
+    pub fn overlaps(&self, other: &Work) -> bool {
+        if self.lower <= other.lower && self.upper >= other.lower {
+            return true;
+        }
+        false
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<(Work, Work)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut pairs = Vec::new();
+        for ll in parse::lines(Self::DAY, input) {
+            let (elf1, elf2) = ll.split_once(ll.text(), ",")?;
+            pairs.push((Work::parse(&ll, elf1)?, Work::parse(&ll, elf2)?));
+        }
+        Ok(pairs)
+    }
+
+    fn part1(pairs: &Self::Input, _params: &Params) -> Result<impl Display> {
+        let full = pairs
+            .iter()
+            .filter(|(w1, w2)| w1.contains(w2) || w2.contains(w1))
+            .count();
+        Ok(full)
+    }
+
+    fn part2(pairs: &Self::Input, _params: &Params) -> Result<impl Display> {
+        let part = pairs
+            .iter()
+            .filter(|(w1, w2)| w1.overlaps(w2) || w2.overlaps(w1))
+            .count();
+        Ok(part)
+    }
+}
@@ -0,0 +1,257 @@
+//! Day 5: Supply Stacks.
+
+use std::fmt::{self, Display};
+
+use anyhow::{Context, Result};
+use log::debug;
+
+use crate::{
+    debugger::{Debugged, Session},
+    parse::{self, Line},
+    Params, ParseError, Part, Solution,
+};
+
+/// The stacks of crates, each from bottom to top.
+#[derive(Clone, Debug)]
+pub struct Cargoship {
+    stacks: Vec<String>,
+}
+
+/// Move `num` crates from one stack to another (0-based stack indices).
+#[derive(Clone)]
+pub struct Order {
+    pub num: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Order {
+    /// Parses `move 1 from 2 to 1`, checking the stacks exist.
+    fn parse(ll: &Line, stacks: usize) -> Result<Self, ParseError> {
+        let mut words = ll.text().split_ascii_whitespace();
+        let mut field = |keyword: &str| {
+            match words.next() {
+                Some(w) if w == keyword => {}
+                Some(w) => return Err(ll.error(w, format!("expected {keyword:?}"))),
+                None => return Err(ll.error(ll.end(), format!("expected {keyword:?}"))),
+            }
+            let num = words
+                .next()
+                .ok_or_else(|| ll.error(ll.end(), "expected a number"))?;
+            Ok((num, ll.parse::<usize>(num)?))
+        };
+        let (_, num) = field("move")?;
+        let mut stack = |keyword| {
+            let (word, n) = field(keyword)?;
+            if !(1..=stacks).contains(&n) {
+                return Err(ll.error(word, format!("expected a stack from 1 to {stacks}")));
+            }
+            Ok(n - 1)
+        };
+        let from = stack("from")?;
+        let to = stack("to")?;
+        Ok(Order { num, from, to })
+    }
+}
+
+impl fmt::Display for Order {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.num,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+impl Cargoship {
+    fn new(cargo: &[Line]) -> Self {
+        let mut stacks = Vec::new();
+        let mut lines = cargo.iter().rev();
+
+        // first line has stack numbers, use for initialization
+        if let Some(ll) = lines.next() {
+            for _ in ll.text().split_ascii_whitespace() {
+                stacks.push(String::new());
+            }
+        }
+
+        // push cargo onto the stacks (trailing spaces may be missing)
+        for ll in lines {
+            for (i, s) in stacks.iter_mut().enumerate() {
+                match ll.text().chars().nth(i * 4 + 1) {
+                    Some(' ') | None => {}
+                    Some(chr) => s.push(chr),
+                }
+            }
+        }
+
+        Cargoship { stacks }
+    }
+
+    pub fn stacks(&self) -> &[String] {
+        &self.stacks
+    }
+
+    /// Carry out the orders, moving one crate at a time (CrateMover 9000) or
+    /// several at once (`multi_move`, CrateMover 9001).
+    pub fn rearrange(&mut self, orders: &[Order], multi_move: bool) -> Result<()> {
+        for (i, order) in orders.iter().enumerate() {
+            self.apply(order, multi_move)
+                .with_context(|| format!("order {}", i + 1))?;
+        }
+        Ok(())
+    }
+
+    /// Carry out a single order, see [`Cargoship::rearrange`].
+    pub fn apply(&mut self, order: &Order, multi_move: bool) -> Result<()> {
+        let mut cargo = String::new();
+        for _ in 0..order.num {
+            let chr = self.stacks[order.from]
+                .pop()
+                .with_context(|| format!("stack {} is empty", order.from + 1))?;
+            cargo.push(chr);
+        }
+
+        // CrateMover 9001 moves multiple crates at once (in-order)
+        if multi_move {
+            cargo = cargo.chars().rev().collect();
+        }
+
+        self.stacks[order.to].push_str(&cargo);
+        Ok(())
+    }
+
+    /// The crates on top of the stacks.
+    pub fn tops(&self) -> Result<String> {
+        let mut tops = String::with_capacity(self.stacks.len());
+        for (i, s) in self.stacks.iter().enumerate() {
+            let top = s.chars().last();
+            tops.push(top.with_context(|| format!("stack {} is empty", i + 1))?);
+        }
+        Ok(tops)
+    }
+}
+
+/// Drawn like the puzzle input: the crates in columns, with the stack numbers below.
+impl fmt::Display for Cargoship {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.stacks.iter().map(String::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row: Vec<String> = self
+                .stacks
+                .iter()
+                .map(|s| match s.as_bytes().get(level) {
+                    Some(&c) => format!("[{}]", c as char),
+                    None => "   ".to_owned(),
+                })
+                .collect();
+            writeln!(f, "{}", row.join(" ").trim_end())?;
+        }
+        let numbers: Vec<String> = (1..=self.stacks.len()).map(|i| format!(" {i} ")).collect();
+        writeln!(f, "{}", numbers.join(" ").trim_end())
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    /// The initial ship, and the rearrangement orders.
+    type Input = (Cargoship, Vec<Order>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = parse::lines(Self::DAY, input);
+        let cargo: Vec<Line> = lines
+            .by_ref()
+            .take_while(|ll| !ll.text().is_empty())
+            .collect();
+        let orders: Vec<Line> = lines.collect();
+        if orders.is_empty() {
+            return Err(ParseError::end_of_input(
+                Self::DAY,
+                input,
+                "expected an empty line between stacks and instructions",
+            ));
+        }
+
+        let ship = Cargoship::new(&cargo);
+        debug!("input: {ship:?}");
+        let orders = orders
+            .iter()
+            .map(|ll| Order::parse(ll, ship.stacks.len()))
+            .collect::<Result<_, _>>()?;
+        Ok((ship, orders))
+    }
+
+    fn part1((ship, orders): &Self::Input, _params: &Params) -> Result<impl Display> {
+        let mut part1 = ship.clone();
+        part1.rearrange(orders, false)?;
+        part1.tops()
+    }
+
+    fn part2((ship, orders): &Self::Input, _params: &Params) -> Result<impl Display> {
+        let mut part2 = ship.clone();
+        part2.rearrange(orders, true)?;
+        part2.tops()
+    }
+}
+
+impl Debugged for Day5 {
+    /// Steps through the orders, one at a time.
+    fn session(
+        (ship, orders): &Self::Input,
+        part: Part,
+        _params: &Params,
+    ) -> Result<Box<dyn Session>> {
+        Ok(Box::new(Unloading {
+            ship: ship.clone(),
+            orders: orders.clone(),
+            done: 0,
+            multi_move: part == Part::Two,
+        }))
+    }
+}
+
+/// The ship, partway through the rearrangement.
+struct Unloading {
+    ship: Cargoship,
+    orders: Vec<Order>,
+    /// The number of orders carried out
+    done: usize,
+    multi_move: bool,
+}
+
+impl Session for Unloading {
+    fn unit(&self) -> &'static str {
+        "order"
+    }
+
+    fn step(&mut self) -> Result<bool> {
+        let Some(order) = self.orders.get(self.done) else {
+            return Ok(false);
+        };
+        self.ship.apply(order, self.multi_move)?;
+        self.done += 1;
+        Ok(true)
+    }
+
+    fn show(&self) -> String {
+        let next = match self.orders.get(self.done) {
+            Some(order) => format!("next: {order}"),
+            None => format!("tops: {}", self.ship.tops().unwrap_or_default()),
+        };
+        format!("{}\n{next}\n", self.ship)
+    }
+
+    /// The number of orders carried out, and the height of each stack.
+    fn values(&self) -> Vec<(String, i64)> {
+        let mut values = vec![("order".to_owned(), self.done as i64)];
+        for (i, stack) in self.ship.stacks.iter().enumerate() {
+            values.push((format!("height{}", i + 1), stack.len() as i64));
+        }
+        values
+    }
+}
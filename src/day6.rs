@@ -0,0 +1,78 @@
+//! Day 6: Tuning Trouble.
+
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use log::{debug, trace};
+
+use crate::{Params, ParseError, Solution};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    /// One datastream per line (the example has several).
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.lines().map(str::to_owned).collect())
+    }
+
+    fn part1(streams: &Self::Input, _params: &Params) -> Result<impl Display> {
+        markers(streams, 4)
+    }
+
+    fn part2(streams: &Self::Input, _params: &Params) -> Result<impl Display> {
+        markers(streams, 14)
+    }
+}
+
+/// The marker positions of all streams, comma-separated.
+pub fn markers(streams: &[String], size: usize) -> Result<String> {
+    let positions = streams
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            find_marker_long(s, size)
+                .with_context(|| format!("no marker of size {size} in stream {}", i + 1))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(positions.iter().join(","))
+}
+
+/// The position after the first `size` distinct characters in a row (none if the
+/// size is 0).
+pub fn find_marker_long(stream: &str, size: usize) -> Option<usize> {
+    if size == 0 {
+        return None;
+    }
+    let bytes: Vec<char> = stream.chars().collect();
+    'start: for i in 0..(bytes.len() + 1).saturating_sub(size) {
+        for j in 0..size - 1 {
+            for k in j + 1..size {
+                if bytes[i + j] == bytes[i + k] {
+                    // one pair of chars equal, continue to next window
+                    continue 'start;
+                }
+            }
+        }
+        // all chars in this window unequal
+        let marker = || bytes[i..i + size].iter().collect::<String>();
+        debug!("marker {} at {}", marker(), i + size);
+        return Some(i + size);
+    }
+    None
+}
+
+#[allow(dead_code)]
+fn find_marker(stream: &str) -> Option<usize> {
+    trace!("input: {}", stream);
+    for (num, (c1, c2, c3, c4)) in stream.chars().tuple_windows().enumerate() {
+        if c1 != c2 && c1 != c3 && c1 != c4 && c2 != c3 && c2 != c4 && c3 != c4 {
+            debug!("marker {c1}{c2}{c3}{c4} found, position {}", num + 4);
+            return Some(num + 4);
+        }
+    }
+    None
+}
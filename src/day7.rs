@@ -0,0 +1,290 @@
+//! Day 7: No Space Left On Device.
+
+use anyhow::{ensure, Context, Result};
+use log::{debug, log_enabled, trace, Level};
+use std::{fmt, mem};
+
+use crate::{parse, Params, ParseError, Solution};
+
+/// A file, or a directory with its contents.
+pub enum Node {
+    File { name: String, size: u32 },
+    Dir { name: String, contents: Vec<Node> },
+}
+
+impl Node {
+    /// An empty directory.
+    pub fn new_dir(name: &str) -> Self {
+        Node::Dir {
+            name: name.to_owned(),
+            contents: Vec::new(),
+        }
+    }
+
+    /// Add a file to this directory.
+    ///
+    /// # Panics
+    ///
+    /// If this is a file.
+    pub fn add_file(&mut self, name: &str, size: u32) {
+        match self {
+            Node::Dir { contents, .. } => {
+                let new_file = Node::File {
+                    name: name.to_owned(),
+                    size,
+                };
+                contents.push(new_file);
+            }
+            _ => panic!("not a directory"),
+        }
+    }
+
+    /// Add an empty subdirectory to this directory.
+    ///
+    /// # Panics
+    ///
+    /// If this is a file.
+    pub fn add_dir(&mut self, name: &str) {
+        match self {
+            Node::Dir { contents, .. } => {
+                let new_dir = Node::Dir {
+                    name: name.to_owned(),
+                    contents: Vec::new(),
+                };
+                contents.push(new_dir);
+            }
+            _ => panic!("not a directory"),
+        }
+    }
+
+    /// The subdirectory of this directory with the given name.
+    ///
+    /// # Panics
+    ///
+    /// If this is a file.
+    pub fn subdir_mut(&mut self, name: &str) -> Option<&mut Node> {
+        match self {
+            Node::Dir { contents, .. } => contents.iter_mut().find(|node| match node {
+                Node::Dir { name: n, .. } => n == name,
+                _ => false,
+            }),
+            _ => panic!("not a directory"),
+        }
+    }
+
+    fn pretty_print(&self, f: &mut fmt::Formatter<'_>, depth: u8) -> fmt::Result {
+        for _ in 0..depth {
+            f.write_str("  ")?;
+        }
+        match self {
+            Node::File { name, size } => {
+                f.write_fmt(format_args!("- {} (file, size={})\n", name, size))
+            }
+            Node::Dir { name, contents } => {
+                f.write_fmt(format_args!("- {} (dir)\n", name))?;
+                for n in contents {
+                    n.pretty_print(f, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Node::File { name, .. } | Node::Dir { name, .. } => name,
+        }
+    }
+
+    /// The size of the file, or of all files in the directory.
+    pub fn size(&self) -> u32 {
+        match self {
+            Node::File { size, .. } => *size,
+            Node::Dir { contents, .. } => {
+                let mut size = 0;
+                for node in contents {
+                    size += node.size();
+                }
+                size
+            }
+        }
+    }
+
+    /// All directories below this one.
+    pub fn subdirs_recursive(&self) -> Vec<&Node> {
+        let mut res = Vec::new();
+        match self {
+            Node::Dir { contents, .. } => {
+                for node in contents {
+                    if let Node::Dir { .. } = node {
+                        res.push(node);
+                    }
+                    res.extend(node.subdirs_recursive())
+                }
+                res
+            }
+            _ => res,
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pretty_print(f, 0)
+    }
+}
+
+/// Replace cur_node with parent after inserting cur_node into parent.
+fn replace_subnode(mut parent: Node, cur_node: &mut Node) {
+    let subnode = parent
+        .subdir_mut(cur_node.name())
+        .expect("directories are listed before cd");
+    mem::swap(subnode, cur_node);
+    *cur_node = parent;
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    /// The root directory.
+    type Input = Node;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(tree: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        trace!("{tree}");
+        Ok(sum_small_dirs(tree, params.get("small_dir_limit")?))
+    }
+
+    fn part2(tree: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        min_deletable_dir(tree, params.get("disk_size")?, params.get("needed_space")?)
+    }
+}
+
+/// The total size of the directories smaller than `limit`.
+pub fn sum_small_dirs(tree: &Node, limit: u32) -> u32 {
+    if log_enabled!(Level::Debug) {
+        for dir in tree.subdirs_recursive() {
+            debug!("{}: total {}", dir.name(), dir.size());
+        }
+    }
+    tree.subdirs_recursive()
+        .iter()
+        .map(|n| n.size())
+        .filter(|n| *n < limit)
+        .sum()
+}
+
+/// The size of the smallest directory to delete to have `needed` of the disk's
+/// `size` free for the update, or 0 if that much is free already.
+pub fn min_deletable_dir(tree: &Node, size: u32, needed: u32) -> Result<u32> {
+    let root_size = tree.size();
+    ensure!(
+        root_size > needed,
+        "the files are smaller than the space needed"
+    );
+    ensure!(root_size <= size, "the files do not fit on the disk");
+    let Some(size_delta) = needed.checked_sub(size - root_size) else {
+        return Ok(0);
+    };
+    tree.subdirs_recursive()
+        .iter()
+        .map(|n| n.size())
+        .filter(|n| *n > size_delta)
+        .min()
+        .context("At least one directory should be bigger than size_delta")
+}
+
+fn parse(input: &str) -> Result<Node, ParseError> {
+    // dir_stack owns nodes, subnodes are only inserted
+    // into their parents when the stack is unwound
+    let mut dir_stack = Vec::<Node>::new();
+    let mut cur_node = Node::new_dir("/");
+
+    for ll in parse::lines(Day7::DAY, input) {
+        let mut words = ll.text().split_ascii_whitespace();
+        match words.next() {
+            Some("$") => {
+                // commands
+                match words.next() {
+                    Some("cd") => {
+                        match words.next() {
+                            Some("..") => {
+                                let parent = dir_stack
+                                    .pop()
+                                    .ok_or_else(|| ll.error(ll.text(), "already at the root"))?;
+                                replace_subnode(parent, &mut cur_node);
+                            }
+                            Some("/") => {
+                                while let Some(parent) = dir_stack.pop() {
+                                    replace_subnode(parent, &mut cur_node);
+                                }
+                            }
+                            Some(name) => {
+                                if cur_node.subdir_mut(name).is_none() {
+                                    return Err(ll.error(name, "directory not listed before"));
+                                }
+                                // push cur to dir stack
+                                dir_stack.push(cur_node);
+                                cur_node = Node::new_dir(name);
+                            }
+                            None => return Err(ll.error(ll.end(), "cd expects a parameter")),
+                        }
+                    }
+                    Some("ls") => {
+                        // done here
+                    }
+                    Some(e) => return Err(ll.error(e, "unsupported command")),
+                    None => return Err(ll.error(ll.end(), "expected a command")),
+                }
+            }
+            Some(first) => {
+                // output
+                let name = words
+                    .next()
+                    .ok_or_else(|| ll.error(ll.end(), "expected a name"))?;
+                match first {
+                    "dir" => {
+                        cur_node.add_dir(name);
+                    }
+                    fsize => {
+                        cur_node.add_file(name, ll.parse(fsize)?);
+                    }
+                }
+            }
+            None => return Err(ll.error(ll.text(), "empty line")),
+        }
+        if let Some(extra) = words.next() {
+            return Err(ll.error(extra, "unexpected text"));
+        }
+    }
+
+    // unwind dir stack
+    while let Some(parent) = dir_stack.pop() {
+        replace_subnode(parent, &mut cur_node);
+    }
+    Ok(cur_node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    #[test]
+    fn snapshot_tree() {
+        let tree = Day7::parse(&crate::example(7).unwrap()).unwrap();
+        assert_snapshot("day7_tree", &tree.to_string());
+    }
+
+    #[test]
+    fn enough_free_space() {
+        let tree = Day7::parse("$ cd /\n$ ls\n100 a\n").unwrap();
+        assert_eq!(min_deletable_dir(&tree, 70000000, 50).unwrap(), 0);
+        assert_eq!(min_deletable_dir(&tree, 200, 99).unwrap(), 0);
+        assert!(min_deletable_dir(&tree, 50, 10).is_err());
+    }
+}
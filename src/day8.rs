@@ -0,0 +1,201 @@
+//! Day 8: Treetop Tree House.
+
+use std::fmt;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use log::trace;
+
+use crate::{
+    geom::Direction,
+    grid::{Grid, Pos},
+    image::{Image, Pictured, Rgb},
+    Params, ParseError, Part, Solution,
+};
+
+#[derive(Clone)]
+pub struct Tree {
+    height: u32,
+    visible: bool,
+    scenic_score: usize,
+}
+
+impl Tree {
+    fn new(height: u32) -> Self {
+        Tree {
+            height,
+            visible: true,
+            scenic_score: 0,
+        }
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Whether the tree is visible from outside the forest, see [`Forest::calc_visible`].
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// See [`Forest::calc_scenic`].
+    pub fn scenic_score(&self) -> usize {
+        self.scenic_score
+    }
+}
+
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.visible {
+            f.write_fmt(format_args!("{}", self.height))
+        } else {
+            // print hidden trees in green
+            f.write_fmt(format_args!("\x1b[32m{}\x1b[39m", self.height))
+        }
+    }
+}
+
+/// The trees of the forest.
+#[derive(Clone)]
+pub struct Forest(Grid<Tree>);
+
+impl Forest {
+    fn parse_trees(input: &str) -> Result<Self, ParseError> {
+        let trees = Grid::parse(Day8::DAY, input, |_, c| {
+            let height = c.to_digit(10).context("expected a tree height")?;
+            Ok(Tree::new(height))
+        })?;
+        Ok(Forest(trees))
+    }
+
+    pub fn trees(&self) -> &Grid<Tree> {
+        &self.0
+    }
+
+    /// Whether a tree at least as high as the one at `pos` stands in direction `dir`.
+    fn blocked(&self, pos: Pos, dir: Direction) -> bool {
+        let height = self.0[pos].height;
+        self.0
+            .ray(pos, dir.step())
+            .any(|(_, tree)| tree.height >= height)
+    }
+
+    /// The number of trees visible from `pos` in direction `dir`.
+    fn viewing_distance(&self, pos: Pos, dir: Direction) -> usize {
+        let height = self.0[pos].height;
+        self.0
+            .ray(pos, dir.step())
+            .find_or_last(|(_, tree)| tree.height >= height)
+            .map_or(0, |(p, _)| p.manhattan(pos))
+    }
+
+    /// Work out which trees are visible from outside the forest.
+    pub fn calc_visible(&mut self) {
+        // trees on the edge are never blocked in the outward direction
+        let visible: Vec<bool> = self
+            .0
+            .positions()
+            .map(|pos| !Direction::ALL.iter().all(|&dir| self.blocked(pos, dir)))
+            .collect();
+        for (tree, visible) in self.0.values_mut().zip(visible) {
+            tree.visible = visible;
+        }
+    }
+
+    /// Work out the scenic score of every tree.
+    pub fn calc_scenic(&mut self) {
+        // trees on the edge have a viewing distance of zero (and thus a zero score)
+        let scores: Vec<usize> = self
+            .0
+            .positions()
+            .map(|pos| {
+                Direction::ALL
+                    .iter()
+                    .map(|&dir| self.viewing_distance(pos, dir))
+                    .product()
+            })
+            .collect();
+        for (tree, score) in self.0.values_mut().zip(scores) {
+            tree.scenic_score = score;
+        }
+    }
+
+    pub fn count_visible(&self) -> usize {
+        self.0.values().filter(|t| t.visible).count()
+    }
+
+    pub fn max_scenic(&self) -> usize {
+        self.0.values().map(|t| t.scenic_score).max().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Forest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Forest;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Forest::parse_trees(input)
+    }
+
+    fn part1(forest: &Self::Input, _params: &Params) -> Result<impl fmt::Display> {
+        let mut forest = forest.clone();
+        forest.calc_visible();
+        trace!("{forest}");
+        Ok(forest.count_visible())
+    }
+
+    fn part2(forest: &Self::Input, _params: &Params) -> Result<impl fmt::Display> {
+        let mut forest = forest.clone();
+        forest.calc_scenic();
+        Ok(forest.max_scenic())
+    }
+}
+
+impl Pictured for Day8 {
+    /// Visible trees in green and hidden ones in grey (part 1), or the scenic
+    /// scores from black to yellow (part 2). Taller trees are brighter.
+    fn image(forest: &Self::Input, part: Part, _params: &Params) -> Result<Image> {
+        let mut forest = forest.clone();
+        let image = match part {
+            Part::One => {
+                forest.calc_visible();
+                Image::from_grid(&forest.0, |_, tree| {
+                    let shade = f64::from(tree.height + 1) / 10.0;
+                    match tree.visible {
+                        true => Rgb::BLACK.mix(Rgb(60, 200, 60), shade),
+                        false => Rgb::BLACK.mix(Rgb(120, 120, 120), shade),
+                    }
+                })
+            }
+            Part::Two => {
+                forest.calc_scenic();
+                let max = forest.max_scenic().max(1) as f64;
+                Image::from_grid(&forest.0, |_, tree| {
+                    Rgb::BLACK.mix(Rgb(255, 220, 0), (tree.scenic_score as f64 / max).sqrt())
+                })
+            }
+        };
+        Ok(image.with_scale(8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    #[test]
+    fn snapshot_visible() {
+        let mut forest = Day8::parse(&crate::example(8).unwrap()).unwrap();
+        forest.calc_visible();
+        assert_snapshot("day8_visible", &forest.to_string());
+    }
+}
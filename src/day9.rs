@@ -0,0 +1,211 @@
+//! Day 9: Rope Bridge.
+
+use anyhow::{ensure, Result};
+use log::debug;
+use std::{collections::HashSet, fmt};
+
+use crate::{
+    animate::{Animated, Animation},
+    geom::{Direction, Point2},
+    parse, Params, ParseError, Part, Solution,
+};
+
+pub type Point = Point2<i32>;
+
+/// Move `knot` one step towards `other` unless they are touching.
+pub fn follow(knot: &mut Point, other: Point) {
+    if knot.chebyshev(other) > 1 {
+        let dist = other - *knot;
+        *knot += Point::new(dist.x.signum(), dist.y.signum());
+    }
+}
+
+/// A rope, and the positions its tail has visited.
+pub struct Grid {
+    rope: Vec<Point>,
+    visited: HashSet<Point>,
+}
+
+impl Grid {
+    /// A rope of `len` knots at the origin.
+    pub fn new(len: usize) -> Self {
+        let rope = vec![Point::default(); len];
+        let visited = HashSet::new();
+
+        Grid { rope, visited }
+    }
+
+    pub fn do_move(&mut self, dir: Direction, dist: usize) {
+        for _ in 0..dist {
+            self.step(dir);
+        }
+    }
+
+    /// Moves the head by one step, and the other knots after it.
+    pub fn step(&mut self, dir: Direction) {
+        self.rope[0] += dir.step();
+        for i in 1..self.rope.len() {
+            // iterate through all pairs of knots in the rope
+            let head = self.rope[i - 1];
+            follow(&mut self.rope[i], head);
+        }
+        if let Some(&tail) = self.rope.last() {
+            self.visited.insert(tail);
+        }
+    }
+
+    /// The knots, from head to tail.
+    pub fn rope(&self) -> &[Point] {
+        &self.rope
+    }
+
+    pub fn visited(&self) -> &HashSet<Point> {
+        &self.visited
+    }
+
+    /// Draws the `width` × `height` area centred on the head: the knots (`H`, `1`,
+    /// `2`, …, `T`), the start `s` and the positions the tail visited (`#`).
+    fn draw(&self, width: i32, height: i32) -> String {
+        let head = self.rope[0];
+        let corner = head - Point::new(width / 2, height / 2);
+        let mut frame = String::new();
+        for y in corner.y..corner.y + height {
+            for x in corner.x..corner.x + width {
+                let p = Point::new(x, y);
+                let c = match self.rope.iter().position(|&knot| knot == p) {
+                    Some(0) => 'H',
+                    Some(i) if i == self.rope.len() - 1 => 'T',
+                    Some(i) => char::from_digit(i as u32 % 10, 10).unwrap(),
+                    None if p == Point::default() => 's',
+                    None if self.visited.contains(&p) => '#',
+                    None => '.',
+                };
+                frame.push(c);
+            }
+            frame.push('\n');
+        }
+        frame
+    }
+}
+
+/// The rope following the head's moves, one step at a time.
+struct Motion {
+    grid: Grid,
+    moves: Vec<(Direction, usize)>,
+    /// The current move, and the steps taken in it
+    next: usize,
+    taken: usize,
+}
+
+impl Animation for Motion {
+    fn frame(&self) -> String {
+        let mut frame = self.grid.draw(64, 24);
+        frame += &format!(
+            "move {}/{}, the tail visited {} positions\n",
+            self.next.min(self.moves.len() - 1) + 1,
+            self.moves.len(),
+            self.grid.visited.len()
+        );
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        while let Some(&(dir, dist)) = self.moves.get(self.next) {
+            if self.taken < dist {
+                self.taken += 1;
+                self.grid.step(dir);
+                return true;
+            }
+            self.next += 1;
+            self.taken = 0;
+        }
+        false
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "head --> ")?;
+        for p in &self.rope {
+            write!(f, "{} ", p)?;
+        }
+        write!(f, "<-- tail")
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    /// The head's moves: a direction and a number of steps each.
+    type Input = Vec<(Direction, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut moves = Vec::new();
+        for ll in parse::lines(Self::DAY, input) {
+            let (dir, dist) = ll.split_once(ll.text(), " ")?;
+            moves.push((ll.parse(dir)?, ll.parse(dist)?));
+        }
+        Ok(moves)
+    }
+
+    fn part1(moves: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        Ok(simulate(moves, knots(params, Part::One)?))
+    }
+
+    fn part2(moves: &Self::Input, params: &Params) -> Result<impl fmt::Display> {
+        Ok(simulate(moves, knots(params, Part::Two)?))
+    }
+}
+
+/// The length of the rope in the given part.
+fn knots(params: &Params, part: Part) -> Result<usize> {
+    let len = match part {
+        Part::One => params.get("part1_knots")?,
+        Part::Two => params.get("part2_knots")?,
+    };
+    ensure!(len > 0, "the rope needs at least one knot");
+    Ok(len)
+}
+
+impl Animated for Day9 {
+    fn animation(moves: &Self::Input, part: Part, params: &Params) -> Result<Box<dyn Animation>> {
+        ensure!(!moves.is_empty(), "the head does not move");
+        Ok(Box::new(Motion {
+            grid: Grid::new(knots(params, part)?),
+            moves: moves.clone(),
+            next: 0,
+            taken: 0,
+        }))
+    }
+}
+
+/// Returns the number of positions visited by the tail of a rope of length `len`.
+pub fn simulate(moves: &[(Direction, usize)], len: usize) -> usize {
+    let mut g = Grid::new(len);
+    for (dir, dist) in moves {
+        g.do_move(*dir, *dist);
+    }
+    debug!("{g}");
+    g.visited.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    #[test]
+    fn snapshot_ropes() {
+        let moves = Day9::parse(&crate::example(9).unwrap()).unwrap();
+        let mut ropes = String::new();
+        for len in [2, 10] {
+            let mut g = Grid::new(len);
+            for &(dir, dist) in &moves {
+                g.do_move(dir, dist);
+                ropes += &format!("{g}\n");
+            }
+        }
+        assert_snapshot("day9_ropes", &ropes);
+    }
+}
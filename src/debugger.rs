@@ -0,0 +1,424 @@
+//! An interactive step debugger for the simulations: step forward one unit (an
+//! order, a cycle, a round or a unit of sand) at a time, run to a step or until a
+//! condition holds, show the state, and go back to an earlier step.
+//!
+//! Going back replays the simulation from the start, so sessions need no history.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+
+use crate::{day10::Day10, day11::Day11, day14::Day14, day5::Day5, Params, Part, Solution};
+
+/// A simulation that can be advanced one unit at a time.
+pub trait Session {
+    /// What one step advances by, e.g. "round".
+    fn unit(&self) -> &'static str;
+
+    /// Advances by one unit. Returns false (leaving the state as it is) once the
+    /// simulation is over.
+    fn step(&mut self) -> Result<bool>;
+
+    /// The current state, for reading.
+    fn show(&self) -> String;
+
+    /// Named numbers describing the current state, which conditions can test.
+    fn values(&self) -> Vec<(String, i64)>;
+}
+
+/// A day whose simulation can be debugged step by step.
+pub trait Debugged: Solution {
+    /// A session at the start of the simulation of the given part.
+    fn session(input: &Self::Input, part: Part, params: &Params) -> Result<Box<dyn Session>>;
+}
+
+type DebuggerFn = fn(&str, Part, &Params) -> Result<Debugger>;
+
+fn debugged<D: Debugged>(input: &str, part: Part, params: &Params) -> Result<Debugger> {
+    let input = D::parse(input)?;
+    let params = params.clone();
+    Debugger::new(move || D::session(&input, part, &params))
+}
+
+static DEBUGGERS: [(u8, DebuggerFn); 4] = [
+    (5, debugged::<Day5>),
+    (10, debugged::<Day10>),
+    (11, debugged::<Day11>),
+    (14, debugged::<Day14>),
+];
+
+/// All days that can be debugged, in ascending order.
+pub fn days() -> impl Iterator<Item = u8> {
+    DEBUGGERS.iter().map(|d| d.0)
+}
+
+/// A debugger for one part of the given day's puzzle, for the given input.
+pub fn debugger(day: u8, part: Part, input: &str, params: &Params) -> Result<Debugger> {
+    let (_, debugger) = DEBUGGERS
+        .iter()
+        .find(|d| d.0 == day)
+        .with_context(|| format!("no debugger for day {day}"))?;
+    debugger(input, part, params)
+}
+
+/// A test of one of the [`Session::values`], e.g. `inspected3 >= 50`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    name: String,
+    op: String,
+    value: i64,
+}
+
+impl Condition {
+    pub fn holds(&self, session: &dyn Session) -> Result<bool> {
+        let values = session.values();
+        let Some(&(_, actual)) = values.iter().find(|(name, _)| *name == self.name) else {
+            let names: Vec<&str> = values.iter().map(|(name, _)| name.as_str()).collect();
+            bail!("unknown value {}: expected {}", self.name, names.join(", "));
+        };
+        let ordering = actual.cmp(&self.value);
+        Ok(match self.op.as_str() {
+            "==" => ordering == Ordering::Equal,
+            "!=" => ordering != Ordering::Equal,
+            "<" => ordering == Ordering::Less,
+            "<=" => ordering != Ordering::Greater,
+            ">" => ordering == Ordering::Greater,
+            _ => ordering != Ordering::Less,
+        })
+    }
+}
+
+impl FromStr for Condition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let [name, op, value] = words[..] else {
+            bail!("expected a condition like \"x == 15\"");
+        };
+        ensure!(
+            ["==", "!=", "<", "<=", ">", ">="].contains(&op),
+            "unknown comparison {op}: expected ==, !=, <, <=, > or >="
+        );
+        let value = value
+            .parse()
+            .map_err(|_| anyhow!("expected a number, found {value}"))?;
+        Ok(Condition {
+            name: name.to_owned(),
+            op: op.to_owned(),
+            value,
+        })
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.name, self.op, self.value)
+    }
+}
+
+/// Debugger commands, one per input line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Step forward by this many units.
+    Step(usize),
+    /// Go back by this many units.
+    Back(usize),
+    /// Go forward or back to the given step.
+    Goto(usize),
+    /// Step forward until the condition holds.
+    Until(Condition),
+    /// Step forward to the end.
+    Run,
+    Show,
+    Values,
+    Help,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (command, arg) = s.trim().split_once(' ').unwrap_or((s.trim(), ""));
+        let arg = arg.trim();
+        let count = |default: Option<usize>| match (arg, default) {
+            ("", Some(default)) => Ok(default),
+            (arg, _) => arg
+                .parse()
+                .map_err(|_| anyhow!("{command} expects a number of steps")),
+        };
+        Ok(match command {
+            // an empty line steps once
+            "" | "s" | "step" => Command::Step(count(Some(1))?),
+            "b" | "back" => Command::Back(count(Some(1))?),
+            "g" | "goto" => Command::Goto(count(None)?),
+            "u" | "until" => Command::Until(arg.parse()?),
+            "r" | "run" => Command::Run,
+            "p" | "show" => Command::Show,
+            "v" | "values" => Command::Values,
+            "h" | "help" => Command::Help,
+            "q" | "quit" => Command::Quit,
+            other => bail!("unknown command: {other} (h for help)"),
+        })
+    }
+}
+
+const HELP: &str = "\
+[enter], s [n]  step forward (n units)
+b [n]           go back (n units)
+g <n>           go to step n
+u <condition>   step until the condition holds, e.g. u x >= 15
+r               run to the end
+p               show the state
+v               show the values conditions can test
+q               quit";
+
+/// Steps through a [`Session`], going back by restarting it.
+pub struct Debugger {
+    start: Box<dyn Fn() -> Result<Box<dyn Session>>>,
+    session: Box<dyn Session>,
+    steps: usize,
+    finished: bool,
+}
+
+impl Debugger {
+    /// A debugger at the start of the sessions created by `start`.
+    pub fn new(start: impl Fn() -> Result<Box<dyn Session>> + 'static) -> Result<Self> {
+        let session = start()?;
+        Ok(Debugger {
+            start: Box::new(start),
+            session,
+            steps: 0,
+            finished: false,
+        })
+    }
+
+    pub fn session(&self) -> &dyn Session {
+        self.session.as_ref()
+    }
+
+    /// The number of steps taken since the start.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Whether the last step found the simulation to be over.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Steps forward by one unit. Returns false at the end.
+    pub fn step(&mut self) -> Result<bool> {
+        if self.finished {
+            return Ok(false);
+        }
+        let stepped = self
+            .session
+            .step()
+            .with_context(|| format!("{} {} failed", self.session.unit(), self.steps + 1))?;
+        match stepped {
+            true => self.steps += 1,
+            false => self.finished = true,
+        }
+        Ok(stepped)
+    }
+
+    /// Goes to the given step, or to the end if the simulation is shorter.
+    pub fn goto(&mut self, step: usize) -> Result<()> {
+        if step < self.steps {
+            self.session = (self.start)()?;
+            self.steps = 0;
+            self.finished = false;
+        }
+        while self.steps < step && self.step()? {}
+        Ok(())
+    }
+
+    /// Steps forward until the condition holds. Returns false if it does not
+    /// before the end.
+    pub fn until(&mut self, condition: &Condition) -> Result<bool> {
+        // check the name before running to the end
+        condition.holds(self.session())?;
+        while self.step()? {
+            if condition.holds(self.session())? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Where the debugger is, e.g. "round 20 (finished)".
+    pub fn position(&self) -> String {
+        let end = if self.finished { " (finished)" } else { "" };
+        format!("{} {}{end}", self.session.unit(), self.steps)
+    }
+
+    /// Executes a command. Returns false to quit.
+    pub fn execute(&mut self, command: &Command, out: &mut impl Write) -> Result<bool> {
+        match command {
+            Command::Step(n) => self.goto(self.steps.saturating_add(*n))?,
+            Command::Back(n) => self.goto(self.steps.saturating_sub(*n))?,
+            Command::Goto(step) => self.goto(*step)?,
+            Command::Until(condition) => {
+                if !self.until(condition)? {
+                    writeln!(out, "{condition} does not hold before the end")?;
+                }
+            }
+            Command::Run => while self.step()? {},
+            Command::Show => {
+                write!(out, "{}", self.session.show())?;
+                return Ok(true);
+            }
+            Command::Values => {
+                for (name, value) in self.session.values() {
+                    writeln!(out, "{name} = {value}")?;
+                }
+                return Ok(true);
+            }
+            Command::Help => {
+                writeln!(out, "{HELP}")?;
+                return Ok(true);
+            }
+            Command::Quit => return Ok(false),
+        }
+        writeln!(out, "{}", self.position())?;
+        Ok(true)
+    }
+
+    /// Reads commands from `input` until it ends or says to quit. Errors are
+    /// reported to `out`, and the session goes on.
+    pub fn repl(&mut self, input: impl BufRead, out: &mut impl Write) -> Result<()> {
+        writeln!(out, "{} (h for help)", self.position())?;
+        write!(out, "> ")?;
+        out.flush()?;
+        for line in input.lines() {
+            let result = line?
+                .parse::<Command>()
+                .and_then(|command| self.execute(&command, out));
+            match result {
+                Ok(false) => return Ok(()),
+                Ok(true) => {}
+                Err(e) => writeln!(out, "error: {e:#}")?,
+            }
+            write!(out, "> ")?;
+            out.flush()?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts down, failing at 13.
+    struct Countdown(i64);
+
+    impl Session for Countdown {
+        fn unit(&self) -> &'static str {
+            "tick"
+        }
+
+        fn step(&mut self) -> Result<bool> {
+            ensure!(self.0 != 13, "unlucky");
+            if self.0 == 0 {
+                return Ok(false);
+            }
+            self.0 -= 1;
+            Ok(true)
+        }
+
+        fn show(&self) -> String {
+            format!("{} left\n", self.0)
+        }
+
+        fn values(&self) -> Vec<(String, i64)> {
+            vec![("left".to_owned(), self.0)]
+        }
+    }
+
+    fn repl(start: i64, commands: &str) -> String {
+        let mut debugger = Debugger::new(move || Ok(Box::new(Countdown(start)))).unwrap();
+        let mut out = Vec::new();
+        debugger.repl(commands.as_bytes(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!("".parse::<Command>().unwrap(), Command::Step(1));
+        assert_eq!("s 5".parse::<Command>().unwrap(), Command::Step(5));
+        assert_eq!("back".parse::<Command>().unwrap(), Command::Back(1));
+        let until = "u  left <= 3".parse::<Command>().unwrap();
+        assert_eq!(until, Command::Until("left <= 3".parse().unwrap()));
+        assert!("g".parse::<Command>().is_err());
+        assert!("u left ~ 3".parse::<Command>().is_err());
+        assert!("jump".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn step_and_go_back() {
+        let out = repl(5, "\ns 2\np\nb\nv\ng 1\nu left == 0\nr\nq\ns\n");
+        let expected = "\
+tick 0 (h for help)
+> tick 1
+> tick 3
+> 2 left
+> tick 2
+> left = 3
+> tick 1
+> tick 5
+> tick 5 (finished)
+> ";
+        assert_eq!(out, expected);
+
+        let out = repl(5, "s 2\ns 18446744073709551615\n");
+        assert!(out.ends_with("> tick 5 (finished)\n> \n"), "{out:?}");
+    }
+
+    #[test]
+    fn report_errors() {
+        let out = repl(15, "u ticks > 1\nu left < 10\np\nu left == 99\n");
+        assert!(
+            out.contains("error: unknown value ticks: expected left\n"),
+            "{out}"
+        );
+        assert!(
+            out.contains("error: tick 3 failed: unlucky\n> 13 left\n"),
+            "{out}"
+        );
+        assert!(
+            out.ends_with("error: tick 3 failed: unlucky\n> \n"),
+            "{out}"
+        );
+
+        let out = repl(2, "u left == 99\n");
+        assert!(out.contains("left == 99 does not hold before the end\ntick 2 (finished)\n"));
+    }
+
+    #[test]
+    fn debug_examples() {
+        for day in days() {
+            let input = crate::example(day).unwrap();
+            let params = Params::load(day, "example").unwrap();
+            for part in Part::BOTH {
+                let mut debugger = debugger(day, part, &input, &params).unwrap();
+                debugger.goto(3).unwrap();
+                let third = debugger.session().show();
+                while debugger.step().unwrap() {}
+                assert!(debugger.finished(), "day {day} part {part}");
+                debugger.goto(3).unwrap();
+                assert_eq!(debugger.session().show(), third, "day {day} part {part}");
+                let values = debugger.session().values();
+                assert_eq!(values[0], (debugger.session().unit().to_owned(), 3));
+            }
+        }
+    }
+}
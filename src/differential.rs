@@ -0,0 +1,233 @@
+//! Differential testing: the solvers against slow, exhaustive reference solutions,
+//! on small generated inputs.
+
+use std::{fmt, ops::Range};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    day11::Day11, day15::Day15, day16::Day16, generate, runner::catch, solver, Params, Part,
+    Solution,
+};
+
+/// A slow but obviously correct solution of a day's puzzle, to check the
+/// [`Solution`] against. It shares the parser.
+pub trait Reference: Solution {
+    fn reference1(input: &Self::Input, params: &Params) -> Result<impl fmt::Display>;
+
+    fn reference2(input: &Self::Input, params: &Params) -> Result<impl fmt::Display>;
+}
+
+/// Solves one part of a day's puzzle for the given input.
+type Solve<'a> = &'a dyn Fn(&str, Part) -> Result<String>;
+
+fn reference<R: Reference>(input: &str, part: Part, params: &Params) -> Result<String> {
+    let input = R::parse(input)?;
+    Ok(match part {
+        Part::One => R::reference1(&input, params)?.to_string(),
+        Part::Two => R::reference2(&input, params)?.to_string(),
+    })
+}
+
+type ReferenceFn = fn(&str, Part, &Params) -> Result<String>;
+
+static REFERENCES: [(u8, ReferenceFn); 3] = [
+    (11, reference::<Day11>),
+    (15, reference::<Day15>),
+    (16, reference::<Day16>),
+];
+
+/// All days that have a reference solution, in ascending order.
+pub fn days() -> impl Iterator<Item = u8> {
+    REFERENCES.iter().map(|r| r.0)
+}
+
+/// Solve one part of the given day's puzzle with the reference solution.
+pub fn solve_reference(day: u8, part: Part, input: &str, params: &Params) -> Result<String> {
+    let (_, solve) = REFERENCES
+        .iter()
+        .find(|r| r.0 == day)
+        .with_context(|| format!("no reference solution for day {day}"))?;
+    solve(input, part, params)
+}
+
+/// An input on which the solver and the reference solution disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub expected: Result<String, String>,
+    pub actual: Result<String, String>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |outcome: &Result<String, String>| match outcome {
+            Ok(answer) => answer.clone(),
+            Err(e) => format!("error: {e}"),
+        };
+        writeln!(f, "day {} part {}:", self.day, self.part)?;
+        writeln!(f, "  reference: {}", show(&self.expected))?;
+        writeln!(f, "  solver:    {}", show(&self.actual))?;
+        write!(f, "input:\n{}", self.input)
+    }
+}
+
+/// Compares the answers to one part; failing both ways counts as agreement.
+fn compare(
+    day: u8,
+    part: Part,
+    input: &str,
+    solve: Solve,
+    reference: Solve,
+) -> Option<Disagreement> {
+    let expected = catch(|| reference(input, part));
+    let actual = catch(|| solve(input, part));
+    let agree = match (&expected, &actual) {
+        (Ok(e), Ok(a)) => e == a,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    };
+    (!agree).then(|| Disagreement {
+        day,
+        part,
+        input: input.to_owned(),
+        expected,
+        actual,
+    })
+}
+
+/// Removes ever smaller runs of lines from the input, as long as the solver and the
+/// reference still disagree.
+fn shrink(mut found: Disagreement, solve: Solve, reference: Solve) -> Disagreement {
+    let mut lines: Vec<String> = found.input.lines().map(str::to_owned).collect();
+    let mut run = lines.len() / 2;
+    while run > 0 {
+        let mut start = 0;
+        while start < lines.len() && lines.len() > run {
+            let mut fewer = lines.clone();
+            fewer.drain(start..(start + run).min(lines.len()));
+            let input = fewer.join("\n") + "\n";
+            match compare(found.day, found.part, &input, solve, reference) {
+                Some(smaller) => {
+                    found = smaller;
+                    lines = fewer;
+                }
+                None => start += run,
+            }
+        }
+        run /= 2;
+    }
+    found
+}
+
+/// Checks the solver of a day against its reference solution, on the inputs
+/// generated from the given seeds with all sizes up to the example's (which are
+/// solved with the example's parameters). Returns the smallest disagreement found,
+/// shrunk further by removing lines.
+pub fn check(day: u8, seeds: Range<u64>) -> Result<Option<Disagreement>> {
+    let solver = solver(day).with_context(|| format!("no solver for day {day}"))?;
+    let (_, reference) = REFERENCES
+        .iter()
+        .find(|r| r.0 == day)
+        .with_context(|| format!("no reference solution for day {day}"))?;
+    let params = Params::load(day, "example")?;
+    search(
+        day,
+        seeds,
+        &|input, part| solver.run(input, part, &params),
+        &|input, part| reference(input, part, &params),
+    )
+}
+
+fn search(
+    day: u8,
+    seeds: Range<u64>,
+    solve: Solve,
+    reference: Solve,
+) -> Result<Option<Disagreement>> {
+    let max_size =
+        generate::example_size(day).with_context(|| format!("no input generator for day {day}"))?;
+    let mut smallest: Option<Disagreement> = None;
+    for seed in seeds {
+        for size in 1..=max_size {
+            // sizes the generator does not support are skipped
+            let Ok(input) = generate::generate(day, seed, size) else {
+                continue;
+            };
+            if smallest
+                .as_ref()
+                .is_some_and(|d| d.input.len() <= input.len())
+            {
+                continue;
+            }
+            if let Some(found) = Part::BOTH
+                .iter()
+                .find_map(|&part| compare(day, part, &input, solve, reference))
+            {
+                smallest = Some(found);
+            }
+        }
+    }
+    Ok(smallest.map(|found| shrink(found, solve, reference)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::Answers;
+
+    use super::*;
+
+    #[test]
+    fn references_match_answers() {
+        let answers = Answers::load().unwrap();
+        // the search area of the real day 15 input is far too big to scan
+        let cases = [
+            (11, "example"),
+            (11, "real"),
+            (15, "example"),
+            (16, "example"),
+            (16, "real"),
+        ];
+        for (day, name) in cases {
+            let input = match name {
+                "example" => crate::example(day).unwrap(),
+                _ => crate::input(day).unwrap(),
+            };
+            let params = Params::load(day, name).unwrap();
+            for part in Part::BOTH {
+                let expected = answers.get(day, name, part).unwrap();
+                let answer = solve_reference(day, part, &input, &params).unwrap();
+                assert_eq!(answer, expected, "day {day} {name} part {part}");
+            }
+        }
+    }
+
+    #[test]
+    fn solvers_agree() {
+        assert_eq!(check(11, 0..3).unwrap(), None);
+        assert_eq!(check(15, 0..3).unwrap(), None);
+        assert_eq!(check(16, 0..3).unwrap(), None);
+    }
+
+    #[test]
+    fn shrink_disagreement() {
+        // wrong as soon as an elf carries a big snack
+        let big = |line: &str| line.parse::<u32>().is_ok_and(|n| n >= 5000);
+        let params = Params::new(1);
+        let wrong = |input: &str, part| match input.lines().any(big) {
+            true => Ok("0".to_owned()),
+            false => crate::solve(1, part, input, &params),
+        };
+        let right = |input: &str, part| crate::solve(1, part, input, &params);
+
+        let found = search(1, 0..3, &wrong, &right).unwrap().unwrap();
+        assert_eq!(found.actual, Ok("0".to_owned()));
+        let lines: Vec<&str> = found.input.lines().collect();
+        assert_eq!(lines.len(), 1);
+        assert!(big(lines[0]));
+
+        assert_eq!(search(1, 0..3, &right, &right).unwrap(), None);
+    }
+}
@@ -0,0 +1,1114 @@
+//! Seeded generators for random puzzle inputs, in the exact format of each day's input.
+//!
+//! The size of an input counts its records: elves, rounds, rucksacks, move orders,
+//! characters, directories, motions, instructions, monkeys, packet pairs, rock paths
+//! or valves. The forest of day 8 is `size` × `size` trees, the heightmap of day 12
+//! has `size` rows of `4 * size` squares, and for day 15 the size is the upper bound
+//! of the search area.
+
+use std::{collections::HashSet, fmt, fmt::Write, ops::RangeInclusive};
+
+use anyhow::{bail, ensure, Context, Result};
+use itertools::Itertools;
+
+use crate::{
+    geom::Point2,
+    grid::{Grid, Pos},
+};
+
+/// How often a generator starts over when its random choices lead nowhere.
+const ATTEMPTS: usize = 100;
+
+/// A small seedable pseudo-random number generator (SplitMix64), so that the
+/// generated inputs only depend on the seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniform number below `n`, which must not be zero.
+    fn below(&mut self, n: u64) -> u64 {
+        // values from `zone` on would make the low results more likely
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    /// A uniform number in the (non-empty) range.
+    pub fn range<T: Uniform>(&mut self, range: RangeInclusive<T>) -> T {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi, "cannot draw from an empty range");
+        let n = match T::span(lo, hi).checked_add(1) {
+            Some(n) => self.below(n),
+            None => self.next_u64(),
+        };
+        T::offset(lo, n)
+    }
+
+    pub fn coin(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+
+    /// A uniform element of the (non-empty) slice.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+/// The integer types [`Rng::range`] can draw.
+pub trait Uniform: Copy + PartialOrd {
+    /// `hi - lo`
+    fn span(lo: Self, hi: Self) -> u64;
+
+    /// `lo + n`
+    fn offset(lo: Self, n: u64) -> Self;
+}
+
+macro_rules! uniform {
+    ($($t:ty),*) => {
+        $(impl Uniform for $t {
+            fn span(lo: Self, hi: Self) -> u64 {
+                (hi as i128 - lo as i128) as u64
+            }
+
+            fn offset(lo: Self, n: u64) -> Self {
+                (lo as i128 + n as i128) as Self
+            }
+        })*
+    };
+}
+
+uniform!(u8, u32, u64, usize, i32, i64);
+
+struct Generator {
+    day: u8,
+    /// A size like the example's, quick to solve.
+    example: usize,
+    /// A size like the real input's.
+    real: usize,
+    generate: fn(&mut Rng, usize) -> Result<String>,
+}
+
+impl Generator {
+    const fn new(
+        day: u8,
+        example: usize,
+        real: usize,
+        generate: fn(&mut Rng, usize) -> Result<String>,
+    ) -> Self {
+        Generator {
+            day,
+            example,
+            real,
+            generate,
+        }
+    }
+}
+
+static GENERATORS: [Generator; 16] = [
+    Generator::new(1, 5, 250, day1),
+    Generator::new(2, 3, 2500, day2),
+    Generator::new(3, 6, 300, day3),
+    Generator::new(4, 6, 1000, day4),
+    Generator::new(5, 4, 500, day5),
+    Generator::new(6, 30, 4096, day6),
+    Generator::new(7, 4, 180, day7),
+    Generator::new(8, 5, 99, day8),
+    Generator::new(9, 8, 2000, day9),
+    Generator::new(10, 146, 140, day10),
+    Generator::new(11, 4, 8, day11),
+    Generator::new(12, 5, 41, day12),
+    Generator::new(13, 8, 150, day13),
+    Generator::new(14, 2, 150, day14),
+    Generator::new(15, 20, 4_000_000, day15),
+    Generator::new(16, 10, 57, day16),
+];
+
+fn generator(day: u8) -> Result<&'static Generator> {
+    GENERATORS
+        .iter()
+        .find(|g| g.day == day)
+        .with_context(|| format!("no input generator for day {day}"))
+}
+
+/// A size like the example input's of the given day, if there is a generator for it.
+pub fn example_size(day: u8) -> Option<usize> {
+    generator(day).ok().map(|g| g.example)
+}
+
+/// The size of the real input of the given day, if there is a generator for it.
+pub fn real_size(day: u8) -> Option<usize> {
+    generator(day).ok().map(|g| g.real)
+}
+
+/// Generate an input for the given day. The same seed and size give the same input.
+pub fn generate(day: u8, seed: u64, size: usize) -> Result<String> {
+    (generator(day)?.generate)(&mut Rng::new(seed), size)
+        .with_context(|| format!("cannot generate an input for day {day}"))
+}
+
+/// Day 1: elves carrying one to ten snacks each.
+fn day1(rng: &mut Rng, size: usize) -> Result<String> {
+    ensure!(size >= 3, "the top three need at least 3 elves");
+    let elves = (0..size).map(|_| {
+        let snacks = rng.range(1..=10);
+        (0..snacks)
+            .map(|_| rng.range(1000..=60000u32).to_string())
+            .join("\n")
+    });
+    Ok(elves.collect::<Vec<_>>().join("\n\n") + "\n")
+}
+
+/// Day 2: rounds of the strategy guide.
+fn day2(rng: &mut Rng, size: usize) -> Result<String> {
+    let mut out = String::new();
+    for _ in 0..size {
+        writeln!(
+            out,
+            "{} {}",
+            rng.pick(&['A', 'B', 'C']),
+            rng.pick(&['X', 'Y', 'Z'])
+        )?;
+    }
+    Ok(out)
+}
+
+/// Day 3: groups of three rucksacks (`size` is rounded up to a multiple of three).
+/// The compartments of a rucksack share exactly one item type, and the rucksacks of
+/// a group exactly one badge.
+fn day3(rng: &mut Rng, size: usize) -> Result<String> {
+    let items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut out = String::new();
+    for _ in 0..size.div_ceil(3) {
+        let badge = *rng.pick(&items);
+        // every other item type is missing from one rucksack of the group
+        let mut missing: Vec<usize> = (0..items.len()).map(|i| i % 3).collect();
+        rng.shuffle(&mut missing);
+
+        for elf in 0..3 {
+            let mut allowed: Vec<char> = items
+                .iter()
+                .zip(&missing)
+                .filter(|&(&item, &m)| item != badge && m != elf)
+                .map(|(&item, _)| item)
+                .collect();
+            rng.shuffle(&mut allowed);
+            let shared = match rng.range(0..=7) {
+                0 => badge,
+                _ => allowed.pop().unwrap(),
+            };
+            // the rest are only found in one of the compartments
+            let (only_first, only_second) = allowed.split_at(allowed.len() / 2);
+            let mut first = vec![shared];
+            let mut second = vec![shared];
+            if shared != badge {
+                match rng.coin() {
+                    true => first.push(badge),
+                    false => second.push(badge),
+                }
+            }
+
+            let half = rng.range(4..=16);
+            for (compartment, pool) in [(&mut first, only_first), (&mut second, only_second)] {
+                while compartment.len() < half {
+                    compartment.push(*rng.pick(pool));
+                }
+                rng.shuffle(compartment);
+            }
+            writeln!(
+                out,
+                "{}{}",
+                String::from_iter(first),
+                String::from_iter(second)
+            )?;
+        }
+    }
+    Ok(out)
+}
+
+/// Day 4: pairs of section assignments.
+fn day4(rng: &mut Rng, size: usize) -> Result<String> {
+    let mut assignment = || {
+        let start = rng.range(1..=99u32);
+        let end = rng.range(start..=99);
+        format!("{start}-{end}")
+    };
+    let mut out = String::new();
+    for _ in 0..size {
+        let first = assignment();
+        let second = assignment();
+        writeln!(out, "{first},{second}")?;
+    }
+    Ok(out)
+}
+
+/// Day 5: a drawing of three to nine stacks, and move orders that never empty a stack.
+fn day5(rng: &mut Rng, size: usize) -> Result<String> {
+    ensure!(size >= 1, "need at least 1 move order");
+    let count = rng.range(3..=9usize);
+    let mut stacks: Vec<Vec<char>> = (0..count)
+        .map(|_| {
+            let height = rng.range(1..=8);
+            (0..height)
+                .map(|_| char::from(rng.range(b'A'..=b'Z')))
+                .collect()
+        })
+        .collect();
+    // some stack needs two crates, so that one can be moved
+    if stacks.iter().all(|s| s.len() == 1) {
+        stacks[0].push(char::from(rng.range(b'A'..=b'Z')));
+    }
+
+    let mut out = String::new();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|s| s.get(level).map_or("   ".to_owned(), |c| format!("[{c}]")))
+            .join(" ");
+        writeln!(out, "{row}")?;
+    }
+    writeln!(out, "{}", (1..=count).map(|i| format!(" {i} ")).join(" "))?;
+    writeln!(out)?;
+
+    for _ in 0..size {
+        let movable: Vec<usize> = (0..count).filter(|&i| stacks[i].len() >= 2).collect();
+        let from = *rng.pick(&movable);
+        let to = loop {
+            let to = rng.range(0..=count - 1);
+            if to != from {
+                break to;
+            }
+        };
+        let num = rng.range(1..=stacks[from].len() - 1);
+        let top = stacks[from].len() - num;
+        let crates = stacks[from].split_off(top);
+        stacks[to].extend(crates);
+        writeln!(out, "move {num} from {} to {}", from + 1, to + 1)?;
+    }
+    Ok(out)
+}
+
+/// Day 6: a datastream of `size` characters with a start-of-message marker.
+fn day6(rng: &mut Rng, size: usize) -> Result<String> {
+    ensure!(
+        size >= 14,
+        "the start-of-message marker needs 14 characters"
+    );
+    let letters: Vec<char> = ('a'..='z').collect();
+    // no marker can hide in a prefix of three letters
+    let start = rng.range(0..=size - 14);
+    let few: Vec<char> = (0..3).map(|_| *rng.pick(&letters)).collect();
+    let mut stream: String = (0..start).map(|_| *rng.pick(&few)).collect();
+
+    let mut marker = letters.clone();
+    rng.shuffle(&mut marker);
+    stream.extend(&marker[..14]);
+    stream.extend((start + 14..size).map(|_| *rng.pick(&letters)));
+    Ok(stream + "\n")
+}
+
+/// A directory of the day 7 file system.
+#[derive(Default)]
+struct Dir {
+    name: String,
+    parent: usize,
+    subdirs: Vec<usize>,
+    /// Names and weights (relative sizes) of the files.
+    files: Vec<(String, u64)>,
+}
+
+/// A lowercase word, optionally with an extension.
+fn file_name(rng: &mut Rng, extension: bool) -> String {
+    let word = |rng: &mut Rng, len| -> String {
+        (0..len)
+            .map(|_| char::from(rng.range(b'a'..=b'z')))
+            .collect()
+    };
+    let len = rng.range(1..=8);
+    let mut name = word(rng, len);
+    if extension && rng.coin() {
+        name = format!("{name}.{}", word(rng, 3));
+    }
+    name
+}
+
+/// Day 7: a shell transcript exploring `size` directories. The files take up
+/// 40000000 to 70000000 bytes, and some directory is large enough to free the
+/// space needed.
+fn day7(rng: &mut Rng, size: usize) -> Result<String> {
+    const TOTAL_SIZE: u64 = 70000000;
+    const MAX_USED: u64 = 40000000;
+    ensure!(size >= 2, "need a directory to delete besides the root");
+
+    for _ in 0..ATTEMPTS {
+        let mut dirs: Vec<Dir> = (0..size).map(|_| Dir::default()).collect();
+        let mut taken: Vec<HashSet<String>> = vec![HashSet::new(); size];
+        let mut unique_name = |rng: &mut Rng, dir: usize, extension: bool| loop {
+            let name = file_name(rng, extension);
+            if taken[dir].insert(name.clone()) {
+                break name;
+            }
+        };
+
+        for i in 1..size {
+            let parent = rng.range(0..=i - 1);
+            dirs[i].name = unique_name(rng, parent, false);
+            dirs[i].parent = parent;
+            dirs[parent].subdirs.push(i);
+        }
+        for (i, dir) in dirs.iter_mut().enumerate() {
+            // the last directory has a file, so that deleting a directory helps
+            let min = usize::from(i == size - 1);
+            for _ in 0..rng.range(min..=4) {
+                let weight = rng.range(1..=1000u64).pow(2);
+                dir.files.push((unique_name(rng, i, true), weight));
+            }
+        }
+
+        // scale the weights to sizes: the root has to be larger than what may be
+        // used, and the largest subdirectory larger than the difference
+        let weight = |dirs: &[Dir]| {
+            let mut totals: Vec<u64> = dirs
+                .iter()
+                .map(|d| d.files.iter().map(|f| f.1).sum())
+                .collect();
+            for i in (1..dirs.len()).rev() {
+                totals[dirs[i].parent] += totals[i];
+            }
+            totals
+        };
+        let totals = weight(&dirs);
+        let largest = totals[1..].iter().max().copied().unwrap_or(0) as f64 / totals[0] as f64;
+        let upper = (MAX_USED as f64 / (1.0 - largest) * 0.99).min(TOTAL_SIZE as f64) as u64;
+        if upper <= MAX_USED + 1000 {
+            continue;
+        }
+        let root = rng.range(MAX_USED + 1000..=upper);
+        for dir in &mut dirs {
+            for file in &mut dir.files {
+                file.1 = (file.1 * root / totals[0]).max(1);
+            }
+        }
+        let totals = weight(&dirs);
+        if !(MAX_USED < totals[0] && totals[0] <= TOTAL_SIZE)
+            || totals[1..].iter().all(|&t| t <= totals[0] - MAX_USED)
+        {
+            continue;
+        }
+
+        let mut out = String::from("$ cd /\n");
+        transcript(rng, &dirs, 0, &mut out)?;
+        return Ok(out);
+    }
+    bail!("no file system found")
+}
+
+/// Lists a directory of the day 7 file system, then explores its subdirectories.
+fn transcript(rng: &mut Rng, dirs: &[Dir], dir: usize, out: &mut String) -> fmt::Result {
+    let mut listing: Vec<String> = dirs[dir]
+        .subdirs
+        .iter()
+        .map(|&d| format!("dir {}", dirs[d].name))
+        .chain(
+            dirs[dir]
+                .files
+                .iter()
+                .map(|(name, size)| format!("{size} {name}")),
+        )
+        .collect();
+    rng.shuffle(&mut listing);
+    writeln!(out, "$ ls")?;
+    for entry in listing {
+        writeln!(out, "{entry}")?;
+    }
+
+    let mut subdirs = dirs[dir].subdirs.clone();
+    rng.shuffle(&mut subdirs);
+    for d in subdirs {
+        writeln!(out, "$ cd {}", dirs[d].name)?;
+        transcript(rng, dirs, d, out)?;
+        writeln!(out, "$ cd ..")?;
+    }
+    Ok(())
+}
+
+/// Day 8: a square forest of tree heights.
+fn day8(rng: &mut Rng, size: usize) -> Result<String> {
+    ensure!(size >= 1, "the forest needs a tree");
+    let mut out = String::new();
+    for _ in 0..size {
+        let row: String = (0..size)
+            .map(|_| char::from(rng.range(b'0'..=b'9')))
+            .collect();
+        writeln!(out, "{row}")?;
+    }
+    Ok(out)
+}
+
+/// Day 9: motions of the rope's head.
+fn day9(rng: &mut Rng, size: usize) -> Result<String> {
+    let mut out = String::new();
+    for _ in 0..size {
+        writeln!(
+            out,
+            "{} {}",
+            rng.pick(&['U', 'D', 'L', 'R']),
+            rng.range(1..=19)
+        )?;
+    }
+    Ok(out)
+}
+
+/// Day 10: a program of at least `size` instructions, running for at least 240
+/// cycles (the whole screen), with the sprite mostly on the screen.
+fn day10(rng: &mut Rng, size: usize) -> Result<String> {
+    let mut out = String::new();
+    let mut x = 1;
+    let mut cycles = 0;
+    let mut count = 0;
+    while count < size || cycles < 240 {
+        if rng.range(0..=2) == 0 {
+            writeln!(out, "noop")?;
+            cycles += 1;
+        } else {
+            let value = loop {
+                let value = rng.range(-20..=20);
+                if value != 0 && (-1..=40).contains(&(x + value)) {
+                    break value;
+                }
+            };
+            x += value;
+            writeln!(out, "addx {value}")?;
+            cycles += 2;
+        }
+        count += 1;
+    }
+    Ok(out)
+}
+
+/// The divisors of the day 11 monkeys. Their product must stay small enough to
+/// square worry levels below it.
+const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+enum Operation {
+    Add(u64),
+    Multiply(u64),
+    Square,
+}
+
+struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    divisor: u64,
+    /// The monkeys to throw to if the test is true or false.
+    targets: [usize; 2],
+}
+
+/// Whether no worry level overflows in the 20 rounds of part 1 (part 2 keeps them
+/// below the product of the divisors).
+fn stays_calm(monkeys: &[Monkey]) -> bool {
+    let mut items: Vec<Vec<u64>> = monkeys.iter().map(|m| m.items.clone()).collect();
+    for _ in 0..20 {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[i]) {
+                let item = match monkey.operation {
+                    Operation::Add(n) => item.checked_add(n),
+                    Operation::Multiply(n) => item.checked_mul(n),
+                    Operation::Square => item.checked_mul(item),
+                };
+                let Some(item) = item.map(|item| item / 3) else {
+                    return false;
+                };
+                let target = monkey.targets[usize::from(item % monkey.divisor != 0)];
+                items[target].push(item);
+            }
+        }
+    }
+    true
+}
+
+/// Day 11: two to nine monkeys, with distinct prime divisors.
+fn day11(rng: &mut Rng, size: usize) -> Result<String> {
+    ensure!(
+        (2..=PRIMES.len()).contains(&size),
+        "need 2 to {} monkeys, so that worry levels do not overflow",
+        PRIMES.len()
+    );
+    for _ in 0..ATTEMPTS {
+        let mut divisors = PRIMES;
+        rng.shuffle(&mut divisors);
+        let monkeys: Vec<Monkey> = (0..size)
+            .map(|i| {
+                let items = (0..rng.range(1..=6)).map(|_| rng.range(50..=99)).collect();
+                let operation = match rng.range(0..=7) {
+                    0..=1 => Operation::Square,
+                    2..=4 => Operation::Multiply(rng.range(2..=19)),
+                    _ => Operation::Add(rng.range(1..=8)),
+                };
+                let mut others: Vec<usize> = (0..size).filter(|&j| j != i).collect();
+                rng.shuffle(&mut others);
+                let targets = [others[0], *others.get(1).unwrap_or(&others[0])];
+                Monkey {
+                    items,
+                    operation,
+                    divisor: divisors[i],
+                    targets,
+                }
+            })
+            .collect();
+        if !stays_calm(&monkeys) {
+            continue;
+        }
+
+        let mut blocks = Vec::new();
+        for (i, m) in monkeys.iter().enumerate() {
+            let operation = match m.operation {
+                Operation::Add(n) => format!("old + {n}"),
+                Operation::Multiply(n) => format!("old * {n}"),
+                Operation::Square => "old * old".to_owned(),
+            };
+            let mut block = String::new();
+            writeln!(block, "Monkey {i}:")?;
+            writeln!(block, "  Starting items: {}", m.items.iter().join(", "))?;
+            writeln!(block, "  Operation: new = {operation}")?;
+            writeln!(block, "  Test: divisible by {}", m.divisor)?;
+            writeln!(block, "    If true: throw to monkey {}", m.targets[0])?;
+            writeln!(block, "    If false: throw to monkey {}", m.targets[1])?;
+            blocks.push(block);
+        }
+        return Ok(blocks.join("\n"));
+    }
+    bail!("the worry levels always overflow")
+}
+
+/// Day 12: a heightmap of `size` rows, with a path from `S` to `E` that climbs
+/// at most one step at a time.
+fn day12(rng: &mut Rng, size: usize) -> Result<String> {
+    ensure!(size >= 5, "need at least 5 rows for a path from a to z");
+    let mut heights = Grid::from_fn(4 * size, size, |_| rng.range(b'a'..=b'z'));
+
+    // a random depth-first walk is a long path without loops
+    let start = Pos::new(rng.range(0..=heights.width() - 1), rng.range(0..=size - 1));
+    let target = rng.range(26..=heights.width() * size / 2);
+    let mut visited = Grid::new(heights.width(), size, false);
+    visited[start] = true;
+    let mut path = vec![start];
+    let mut longest = Vec::new();
+    while let Some(&pos) = path.last() {
+        if path.len() == target {
+            longest = path;
+            break;
+        }
+        let next: Vec<Pos> = visited.neighbours4(pos).filter(|&n| !visited[n]).collect();
+        if next.is_empty() {
+            if path.len() > longest.len() {
+                longest = path.clone();
+            }
+            path.pop();
+        } else {
+            let n = *rng.pick(&next);
+            visited[n] = true;
+            path.push(n);
+        }
+    }
+    ensure!(
+        longest.len() >= 26,
+        "no path long enough to climb from a to z"
+    );
+
+    // climb one step at 25 random positions of the path
+    let mut climbs: Vec<bool> = (1..longest.len()).map(|i| i <= 25).collect();
+    rng.shuffle(&mut climbs);
+    let mut height = b'a';
+    for (&pos, climb) in longest[1..].iter().zip(climbs) {
+        height += u8::from(climb);
+        heights[pos] = height;
+    }
+    heights[start] = b'S';
+    heights[*longest.last().unwrap()] = b'E';
+
+    let mut out = String::new();
+    for row in heights.rows() {
+        writeln!(out, "{}", String::from_utf8_lossy(row))?;
+    }
+    Ok(out)
+}
+
+#[derive(Clone)]
+enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    fn random(rng: &mut Rng, depth: usize) -> Self {
+        let len = rng.range(0..=4);
+        Packet::List(
+            (0..len)
+                .map(|_| match depth > 0 && rng.range(0..=2) == 0 {
+                    true => Packet::random(rng, depth - 1),
+                    false => Packet::Integer(rng.range(0..=10)),
+                })
+                .collect(),
+        )
+    }
+
+    /// Change one value deep inside, so that comparisons have to look far.
+    fn tweak(&mut self, rng: &mut Rng) {
+        match self {
+            Packet::Integer(n) => *n = rng.range(0..=10),
+            Packet::List(items) if items.is_empty() || rng.range(0..=3) == 0 => {
+                items.push(Packet::Integer(rng.range(0..=10)));
+            }
+            Packet::List(items) => {
+                let i = rng.range(0..=items.len() - 1);
+                items[i].tweak(rng);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Integer(n) => write!(f, "{n}"),
+            Packet::List(items) => write!(f, "[{}]", items.iter().join(",")),
+        }
+    }
+}
+
+/// Day 13: pairs of packets, half of them nearly equal.
+fn day13(rng: &mut Rng, size: usize) -> Result<String> {
+    let mut pairs = Vec::new();
+    for _ in 0..size {
+        let left = Packet::random(rng, 4);
+        let right = match rng.coin() {
+            true => {
+                let mut right = left.clone();
+                right.tweak(rng);
+                right
+            }
+            false => Packet::random(rng, 4),
+        };
+        pairs.push(format!("{left}\n{right}\n"));
+    }
+    Ok(pairs.join("\n"))
+}
+
+/// Day 14: rock paths of horizontal and vertical lines below the sand source.
+fn day14(rng: &mut Rng, size: usize) -> Result<String> {
+    let depth = (size + 10).min(400);
+    let xs = 500 - depth..=500 + depth;
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut pos = Pos::new(rng.range(xs.clone()), rng.range(1..=depth));
+        let mut path = vec![pos];
+        let mut horizontal = rng.coin();
+        for _ in 0..rng.range(1..=5) {
+            let len = rng.range(1..=8);
+            let next = match (horizontal, rng.coin()) {
+                (true, true) => Pos::new((pos.x + len).min(*xs.end()), pos.y),
+                (true, false) => Pos::new((pos.x - len).max(*xs.start()), pos.y),
+                (false, true) => Pos::new(pos.x, (pos.y + len).min(depth)),
+                (false, false) => Pos::new(pos.x, pos.y.saturating_sub(len).max(1)),
+            };
+            if next != pos {
+                path.push(next);
+                pos = next;
+            }
+            horizontal = !horizontal;
+        }
+        writeln!(
+            out,
+            "{}",
+            path.iter().map(|p| format!("{},{}", p.x, p.y)).join(" -> ")
+        )?;
+    }
+    Ok(out)
+}
+
+type Coord = Point2<i64>;
+
+struct Sensor {
+    position: Coord,
+    beacon: Coord,
+    range: u64,
+}
+
+impl Sensor {
+    fn new(position: Coord, beacon: Coord) -> Self {
+        let range = position.manhattan(beacon);
+        Sensor {
+            position,
+            beacon,
+            range,
+        }
+    }
+
+    fn covers(&self, p: Coord) -> bool {
+        self.position.manhattan(p) <= self.range
+    }
+
+    /// The first and last x covered in row y.
+    fn row(&self, y: i64) -> Option<(i64, i64)> {
+        let width = self.range.checked_sub(y.abs_diff(self.position.y))? as i64;
+        Some((self.position.x - width, self.position.x + width))
+    }
+}
+
+/// A random point at the given distance from the origin.
+fn on_diamond(rng: &mut Rng, distance: i64) -> Coord {
+    let x = rng.range(-distance..=distance);
+    let y = distance - x.abs();
+    Coord::new(x, if rng.coin() { y } else { -y })
+}
+
+/// Uncovered positions `lo..=hi` of row y.
+#[derive(Clone, Copy)]
+struct Gap {
+    y: i64,
+    lo: i64,
+    hi: i64,
+}
+
+/// Remove what the sensor covers from the gaps.
+fn cut(gaps: &mut Vec<Gap>, sensor: &Sensor) {
+    let mut rest = Vec::with_capacity(gaps.len());
+    for gap in gaps.drain(..) {
+        match sensor.row(gap.y) {
+            Some((lo, hi)) if lo <= gap.hi && gap.lo <= hi => {
+                if gap.lo < lo {
+                    rest.push(Gap { hi: lo - 1, ..gap });
+                }
+                if hi < gap.hi {
+                    rest.push(Gap { lo: hi + 1, ..gap });
+                }
+            }
+            _ => rest.push(gap),
+        }
+    }
+    *gaps = rest;
+}
+
+/// Sensors and beacons around the distress beacon. Every sensor's beacon is the
+/// only one nearest to it, and no sensor reaches the distress beacon.
+struct Layout {
+    /// The search area is `0..=upper` in both directions.
+    upper: i64,
+    distress: Coord,
+    sensors: Vec<Sensor>,
+    beacons: Vec<Coord>,
+}
+
+impl Layout {
+    fn new(rng: &mut Rng, upper: i64) -> Self {
+        let distress = Coord::new(rng.range(0..=upper), rng.range(0..=upper));
+        let mut layout = Layout {
+            upper,
+            distress,
+            sensors: Vec::new(),
+            beacons: Vec::new(),
+        };
+        // four sensors on the diagonals cover the square around the distress beacon
+        // (but not the beacon), with their beacons on the far sides
+        let k = rng.range(1..=(upper / 10).max(1));
+        for (sx, sy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let position = distress + Coord::new(sx * k, sy * k);
+            let t = rng.range(0..=2 * k - 1);
+            let beacon = position + Coord::new(sx * t, sy * (2 * k - 1 - t));
+            layout.add(Sensor::new(position, beacon));
+        }
+        layout
+    }
+
+    fn covered(&self, p: Coord) -> bool {
+        self.sensors.iter().any(|s| s.covers(p))
+    }
+
+    fn add(&mut self, sensor: Sensor) {
+        if !self.beacons.contains(&sensor.beacon) {
+            self.beacons.push(sensor.beacon);
+        }
+        self.sensors.push(sensor);
+    }
+
+    /// A sensor at the given position, seeing the nearest of the known beacons.
+    fn sensor_at(&self, position: Coord) -> Option<Sensor> {
+        if self.sensors.iter().any(|s| s.position == position) {
+            return None;
+        }
+        let mut distances: Vec<(u64, Coord)> = self
+            .beacons
+            .iter()
+            .map(|&b| (position.manhattan(b), b))
+            .collect();
+        distances.sort_unstable();
+        let (range, beacon) = *distances.first()?;
+        let next = distances.get(1).map_or(u64::MAX, |d| d.0);
+        (range < next && range < position.manhattan(self.distress))
+            .then(|| Sensor::new(position, beacon))
+    }
+
+    /// A new sensor covering `p`, near it on a logarithmic scale. It sees a known
+    /// beacon if possible, or else a new one out of reach of the other sensors.
+    fn sensor_covering(&self, rng: &mut Rng, p: Coord) -> Option<Sensor> {
+        let scale = self.upper.max(1).ilog2() + 2;
+        let away = |rng: &mut Rng| {
+            let reach = (1 << rng.range(0..=scale)) - 1;
+            rng.range(0..=reach)
+        };
+        for _ in 0..64 {
+            let away = away(rng);
+            let position = p + on_diamond(rng, away);
+            if let Some(sensor) = self.sensor_at(position).filter(|s| s.covers(p)) {
+                return Some(sensor);
+            }
+        }
+        for _ in 0..64 {
+            let away = away(rng).max(1);
+            let position = p + on_diamond(rng, away);
+            let limit = self
+                .beacons
+                .iter()
+                .chain([&self.distress])
+                .map(|&b| position.manhattan(b))
+                .min()?
+                .saturating_sub(1);
+            if limit < away as u64 || self.sensors.iter().any(|s| s.position == position) {
+                continue;
+            }
+            for _ in 0..16 {
+                let range = rng.range(away..=limit as i64);
+                let beacon = position + on_diamond(rng, range);
+                if !self.covered(beacon) {
+                    return Some(Sensor::new(position, beacon));
+                }
+            }
+        }
+        None
+    }
+
+    /// The gaps the sensors leave in the search area, besides the distress beacon.
+    fn gaps(&self) -> Vec<Gap> {
+        let mut gaps = Vec::new();
+        let mut covered = Vec::new();
+        for y in 0..=self.upper {
+            covered.clear();
+            covered.extend(self.sensors.iter().filter_map(|s| s.row(y)));
+            covered.sort_unstable();
+            let mut x = 0;
+            for &(lo, hi) in &covered {
+                if x > self.upper {
+                    break;
+                }
+                if lo > x {
+                    let hi = (lo - 1).min(self.upper);
+                    gaps.push(Gap { y, lo: x, hi });
+                }
+                x = x.max(hi + 1);
+            }
+            if x <= self.upper {
+                gaps.push(Gap {
+                    y,
+                    lo: x,
+                    hi: self.upper,
+                });
+            }
+        }
+        cut(&mut gaps, &Sensor::new(self.distress, self.distress));
+        gaps
+    }
+
+    /// Add sensors until the distress beacon is the only position left uncovered.
+    fn cover(&mut self, rng: &mut Rng) -> bool {
+        // sensors at random points, while those are easy to find
+        let mut misses = 0;
+        while misses < 100 {
+            let p = Coord::new(rng.range(0..=self.upper), rng.range(0..=self.upper));
+            let sensor = match p == self.distress || self.covered(p) {
+                true => None,
+                false => self.sensor_covering(rng, p),
+            };
+            match sensor {
+                Some(sensor) => {
+                    self.add(sensor);
+                    misses = 0;
+                }
+                None => misses += 1,
+            }
+        }
+
+        // then close the remaining gaps one by one
+        let mut gaps = self.gaps();
+        let mut failures = 0;
+        while !gaps.is_empty() {
+            let gap = *rng.pick(&gaps);
+            let p = Coord::new(rng.range(gap.lo..=gap.hi), gap.y);
+            match self.sensor_covering(rng, p) {
+                Some(sensor) => {
+                    cut(&mut gaps, &sensor);
+                    self.add(sensor);
+                    failures = 0;
+                }
+                None if failures < 100 => failures += 1,
+                None => return false,
+            }
+        }
+        true
+    }
+}
+
+/// Day 15: sensors leaving exactly one position of the search area `0..=size`
+/// uncovered. The search areas of the example and the real input are set in
+/// `aoc.toml`, so `size` must be one of them: 20 or 4000000.
+fn day15(rng: &mut Rng, size: usize) -> Result<String> {
+    ensure!(
+        size == 20 || size == 4_000_000,
+        "the search area must be 20 (like the example) or 4000000 (like the real input)"
+    );
+    let upper = i64::try_from(size)?;
+    for _ in 0..ATTEMPTS {
+        let mut layout = Layout::new(rng, upper);
+        if layout.cover(rng) {
+            rng.shuffle(&mut layout.sensors);
+            let mut out = String::new();
+            for s in layout.sensors {
+                writeln!(
+                    out,
+                    "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                    s.position.x, s.position.y, s.beacon.x, s.beacon.y
+                )?;
+            }
+            return Ok(out);
+        }
+    }
+    bail!("no sensor layout found")
+}
+
+/// Day 16: a connected network of `size` valves starting at `AA`, with up to 15
+/// working valves.
+fn day16(rng: &mut Rng, size: usize) -> Result<String> {
+    let mut labels: Vec<String> = ('A'..='Z')
+        .cartesian_product('A'..='Z')
+        .map(|(a, b)| format!("{a}{b}"))
+        .skip(1)
+        .collect();
+    ensure!(
+        (2..=labels.len() + 1).contains(&size),
+        "need 2 to {} valves",
+        labels.len() + 1
+    );
+    rng.shuffle(&mut labels);
+    labels.truncate(size - 1);
+    labels.insert(0, "AA".to_owned());
+
+    // a random tree, plus a few shortcuts
+    let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); size];
+    let mut dig = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..size {
+        dig(i, rng.range(0..=i - 1));
+    }
+    for _ in 0..size / 4 {
+        dig(rng.range(0..=size - 1), rng.range(0..=size - 1));
+    }
+
+    let mut rates = vec![0; size];
+    let mut working: Vec<usize> = (1..size).collect();
+    rng.shuffle(&mut working);
+    for &v in working.iter().take((size / 4 + 1).min(15)) {
+        rates[v] = rng.range(2..=25);
+    }
+
+    let mut order: Vec<usize> = (0..size).collect();
+    rng.shuffle(&mut order);
+    let mut out = String::new();
+    for v in order {
+        let mut neighbours = tunnels[v].clone();
+        rng.shuffle(&mut neighbours);
+        let neighbours = neighbours.iter().map(|&n| &labels[n]).join(", ");
+        let tunnels = match tunnels[v].len() {
+            1 => format!("tunnel leads to valve {neighbours}"),
+            _ => format!("tunnels lead to valves {neighbours}"),
+        };
+        writeln!(
+            out,
+            "Valve {} has flow rate={}; {tunnels}",
+            labels[v], rates[v]
+        )?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Part;
+
+    use super::*;
+
+    #[test]
+    fn draw_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3..=3)));
+            assert_eq!(rng.range(5..=5u8), 5);
+        }
+        rng.range(0..=u64::MAX);
+        rng.range(i64::MIN..=i64::MAX);
+
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(16, 3, 20).unwrap(), generate(16, 3, 20).unwrap());
+        assert_ne!(generate(16, 3, 20).unwrap(), generate(16, 4, 20).unwrap());
+        assert!(generate(15, 3, 1000).is_err());
+        assert!(generate(25, 3, 10).is_err());
+    }
+
+    #[test]
+    fn solve_generated_inputs() {
+        for g in &GENERATORS {
+            for seed in 0..3 {
+                let input = generate(g.day, seed, example_size(g.day).unwrap()).unwrap();
+                let params = crate::Params::load(g.day, "example").unwrap();
+                for part in Part::BOTH {
+                    if let Err(e) = crate::solve(g.day, part, &input, &params) {
+                        panic!("day {} part {part}, seed {seed}: {e:#}\n{input}", g.day);
+                    }
+                }
+            }
+        }
+    }
+}
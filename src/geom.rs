@@ -0,0 +1,333 @@
+//! 2D geometry: points, directions, distances and bounding boxes.
+//!
+//! The y axis grows downwards (like the rows of a [`crate::grid::Grid`]), so
+//! [`Direction::Up`] decreases y.
+
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Sub},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, Context};
+
+/// The integer types that can be used as coordinates.
+pub trait Coordinate: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    /// The type of distances between coordinates (unsigned).
+    type Distance: Copy + Ord + Add<Output = Self::Distance>;
+
+    fn distance(self, other: Self) -> Self::Distance;
+}
+
+macro_rules! coordinate {
+    ($($t:ty => $d:ty),*) => {
+        $(impl Coordinate for $t {
+            type Distance = $d;
+
+            fn distance(self, other: Self) -> $d {
+                self.abs_diff(other)
+            }
+        })*
+    };
+}
+
+coordinate!(i32 => u32, i64 => u64, isize => usize, u32 => u32, u64 => u64, usize => usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    /// Taxicab distance: |dx| + |dy|
+    pub fn manhattan(self, other: Self) -> T::Distance {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// Chessboard distance: max(|dx|, |dy|)
+    pub fn chebyshev(self, other: Self) -> T::Distance {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Parses `x,y` (spaces around the numbers are allowed).
+impl<T: FromStr> FromStr for Point2<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .split_once(',')
+            .context("coordinates should be comma-separated")?;
+        Ok(Point2::new(x.trim().parse()?, y.trim().parse()?))
+    }
+}
+
+/// One of the four horizontal and vertical directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting upwards.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The offset of a single step in this direction.
+    pub fn step<T: From<i8>>(self) -> Point2<T> {
+        Direction8::from(self).step()
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+}
+
+/// Parses the puzzle notation `U`, `D`, `L` and `R`.
+impl FromStr for Direction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" => Ok(Direction::Up),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            "R" => Ok(Direction::Right),
+            e => bail!("invalid direction: {e}"),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        c.encode_utf8(&mut [0; 4]).parse()
+    }
+}
+
+/// One of the eight directions, including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Clockwise, starting upwards.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// The offset of a single step in this direction.
+    pub fn step<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Direction8::Up => (0, -1),
+            Direction8::UpRight => (1, -1),
+            Direction8::Right => (1, 0),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (0, 1),
+            Direction8::DownLeft => (-1, 1),
+            Direction8::Left => (-1, 0),
+            Direction8::UpLeft => (-1, -1),
+        };
+        Point2::new(x.into(), y.into())
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Direction8::Up,
+            Direction::Right => Direction8::Right,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+        }
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(dir: Direction8) -> Result<Self, Self::Error> {
+        Direction::ALL
+            .into_iter()
+            .find(|&d| Direction8::from(d) == dir)
+            .ok_or_else(|| anyhow!("{dir:?} is diagonal"))
+    }
+}
+
+/// The smallest axis-aligned rectangle containing a set of points (bounds inclusive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coordinate> BoundingBox<T> {
+    /// The bounding box of a single point.
+    pub fn new(p: Point2<T>) -> Self {
+        BoundingBox { min: p, max: p }
+    }
+
+    /// The bounding box of all points, or `None` if there are none.
+    pub fn of(points: impl IntoIterator<Item = Point2<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let mut bbox = BoundingBox::new(points.next()?);
+        bbox.extend(points);
+        Some(bbox)
+    }
+
+    /// Grow the box to contain `p`.
+    pub fn include(&mut self, p: Point2<T>) {
+        self.min = Point2::new(self.min.x.min(p.x), self.min.y.min(p.y));
+        self.max = Point2::new(self.max.x.max(p.x), self.max.y.max(p.y));
+    }
+
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// The number of columns covered, minus one.
+    pub fn width(&self) -> T::Distance {
+        self.min.x.distance(self.max.x)
+    }
+
+    /// The number of rows covered, minus one.
+    pub fn height(&self) -> T::Distance {
+        self.min.y.distance(self.max.y)
+    }
+}
+
+impl<T: Coordinate> Extend<Point2<T>> for BoundingBox<T> {
+    fn extend<I: IntoIterator<Item = Point2<T>>>(&mut self, iter: I) {
+        for p in iter {
+            self.include(p);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(-2, 15);
+        let b = Point2::new(2, 18);
+        assert_eq!(a.manhattan(b), 7u32);
+        assert_eq!(a.chebyshev(b), 4u32);
+        assert_eq!(Point2::new(3usize, 0).manhattan(Point2::new(0, 4)), 7);
+    }
+
+    #[test]
+    fn directions() {
+        let dirs: Vec<Direction> = "U R D L".split(' ').map(|d| d.parse().unwrap()).collect();
+        assert_eq!(dirs, Direction::ALL);
+        assert!("X".parse::<Direction>().is_err());
+
+        let mut p = Point2::new(0i32, 0);
+        for dir in [Direction::Up, Direction::Right, Direction::Right] {
+            p += dir.step();
+        }
+        assert_eq!(p, Point2::new(2, -1));
+
+        assert_eq!(Direction::Up.opposite(), Direction::Down);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction8::UpLeft.opposite(), Direction8::DownRight);
+        assert!(Direction::try_from(Direction8::UpLeft).is_err());
+    }
+
+    #[test]
+    fn parse_point() {
+        assert_eq!(
+            "498,4".parse::<Point2<usize>>().unwrap(),
+            Point2::new(498, 4)
+        );
+        assert!("498;4".parse::<Point2<usize>>().is_err());
+        assert!("-1,4".parse::<Point2<usize>>().is_err());
+    }
+
+    #[test]
+    fn bounding_box() {
+        let points = [Point2::new(3i32, -1), Point2::new(-2, 4), Point2::new(0, 0)];
+        let bbox = BoundingBox::of(points).unwrap();
+        assert_eq!(bbox.min, Point2::new(-2, -1));
+        assert_eq!(bbox.max, Point2::new(3, 4));
+        assert_eq!((bbox.width(), bbox.height()), (5u32, 5));
+        assert!(bbox.contains(Point2::new(0, 4)));
+        assert!(!bbox.contains(Point2::new(0, 5)));
+        assert_eq!(BoundingBox::<i32>::of([]), None);
+    }
+}
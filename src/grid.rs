@@ -0,0 +1,328 @@
+//! A dense two-dimensional grid, as used by the puzzles with character maps.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use anyhow::Result;
+
+use crate::{
+    geom::{Direction, Direction8, Point2},
+    parse, ParseError,
+};
+
+/// A position in a [`Grid`]: `x` is the column, `y` the row (counting downwards).
+pub type Pos = Point2<usize>;
+
+/// The offset of a move between two positions, see [`Direction::step`].
+pub type Step = Point2<isize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row-major
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Build a grid by calling `f` for every position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(Pos { x, y }));
+            }
+        }
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parse the given day's character map (one row per line), converting each
+    /// character with `f`. All lines must have the same length.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        mut f: impl FnMut(Pos, char) -> Result<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, ll) in parse::lines(day, input).enumerate() {
+            let mut row_len = 0;
+            for (x, (i, c)) in ll.text().char_indices().enumerate() {
+                let at = &ll.text()[i..i + c.len_utf8()];
+                let cell = f(Pos { x, y }, c).map_err(|e| ll.error(at, format!("{e:#}")))?;
+                cells.push(cell);
+                row_len += 1;
+            }
+            let width = *width.get_or_insert(row_len);
+            if row_len != width {
+                let msg = format!("line has {row_len} characters, expected {width}");
+                return Err(ll.error(ll.text(), msg));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    /// The position one `step` away from `pos`, if it is inside the grid.
+    pub fn offset(&self, pos: Pos, step: Step) -> Option<Pos> {
+        let x = pos.x.checked_add_signed(step.x)?;
+        let y = pos.y.checked_add_signed(step.y)?;
+        Some(Pos { x, y }).filter(|&p| self.contains(p))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Pos {
+            x: i % width,
+            y: i / width,
+        })
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// All cells, row by row.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() panics for a zero width
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The cells from `from` (exclusive) in the direction of `step` up to the border.
+    pub fn ray(&self, from: Pos, step: Step) -> impl Iterator<Item = (Pos, &T)> {
+        std::iter::successors(self.offset(from, step), move |&p| self.offset(p, step))
+            .map(|p| (p, &self[p]))
+    }
+
+    /// The up to four horizontal and vertical neighbours inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d.step()))
+    }
+
+    /// The up to eight neighbours (including diagonals) inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d.step()))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Display the grid row by row, rendering each cell with `f`.
+    pub fn display_with<F, D>(&self, f: F) -> DisplayWith<'_, T, F>
+    where
+        F: Fn(&T) -> D,
+        D: fmt::Display,
+    {
+        DisplayWith { grid: self, f }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Change the size of the grid. Cells keep their positions, new cells are `fill`.
+    pub fn resize(&mut self, width: usize, height: usize, fill: T) {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let cell = match self.get(Pos { x, y }) {
+                    Some(cell) => cell.clone(),
+                    None => fill.clone(),
+                };
+                cells.push(cell);
+            }
+        }
+        *self = Grid {
+            width,
+            height,
+            cells,
+        };
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos} out of bounds"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// See [`Grid::display_with`].
+pub struct DisplayWith<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+}
+
+impl<T, F, D> fmt::Display for DisplayWith<'_, T, F>
+where
+    F: Fn(&T) -> D,
+    D: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.grid.rows() {
+            for cell in row {
+                write!(f, "{}", (self.f)(cell))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+
+    use super::*;
+
+    fn digit(_: Pos, c: char) -> Result<u32> {
+        c.to_digit(10).context("not a digit")
+    }
+
+    fn digits() -> Grid<u32> {
+        Grid::parse(0, "123\n456\n", digit).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let g = digits();
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[Pos::new(2, 1)], 6);
+        assert_eq!(g.get(Pos::new(3, 0)), None);
+        assert_eq!(g.to_string(), "123\n456\n");
+        assert_eq!(g.display_with(|d| d * 2).to_string(), "246\n81012\n");
+
+        assert!(Grid::parse(0, "12\n3\n", |_, c| Ok(c)).is_err());
+        let err = Grid::parse(0, "12\n1x", digit).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (2, 2, "not a digit")
+        );
+    }
+
+    #[test]
+    fn rows_columns_rays() {
+        let g = digits();
+        assert_eq!(g.row(1), &[4, 5, 6]);
+        assert_eq!(g.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        let ray: Vec<_> = g
+            .ray(Pos::new(2, 1), Direction::Left.step())
+            .map(|(_, &d)| d)
+            .collect();
+        assert_eq!(ray, [5, 4]);
+        assert_eq!(g.ray(Pos::new(0, 0), Direction::Up.step()).count(), 0);
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = digits();
+        let n4: Vec<_> = g.neighbours4(Pos::new(0, 0)).collect();
+        assert_eq!(n4, [Pos::new(1, 0), Pos::new(0, 1)]);
+        assert_eq!(g.neighbours8(Pos::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn resize() {
+        let mut g = digits();
+        g.resize(4, 3, 0);
+        assert_eq!(g.to_string(), "1230\n4560\n0000\n");
+        g.resize(1, 1, 0);
+        assert_eq!(g.to_string(), "1\n");
+    }
+}
@@ -0,0 +1,270 @@
+//! Images of grid-shaped puzzle states, written as PPM, PNG or SVG files: for real
+//! inputs that are too big to look at in a terminal.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    day12::Day12,
+    day14::Day14,
+    day8::Day8,
+    grid::{Grid, Pos},
+    Params, Part, Solution,
+};
+
+/// A colour, with eight bits per channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(220, 30, 30);
+
+    /// The colour `t` of the way from `self` to `other` (with `t` between 0 and 1).
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel =
+            |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+}
+
+/// The file formats an [`Image`] can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl Format {
+    /// The format matching the extension of `path`.
+    pub fn of(path: &Path) -> Result<Self> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        Ok(match ext.to_ascii_lowercase().as_str() {
+            "ppm" => Format::Ppm,
+            "png" => Format::Png,
+            "svg" => Format::Svg,
+            _ => bail!(
+                "cannot tell the image format of {}: use .ppm, .png or .svg",
+                path.display()
+            ),
+        })
+    }
+}
+
+/// A grid of coloured cells, drawn as squares of `scale` pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    cells: Grid<Rgb>,
+    scale: usize,
+}
+
+impl Image {
+    /// Colours every cell of a `width` × `height` grid with `colour`.
+    pub fn from_fn(width: usize, height: usize, colour: impl FnMut(Pos) -> Rgb) -> Self {
+        Image {
+            cells: Grid::from_fn(width, height, colour),
+            scale: 1,
+        }
+    }
+
+    /// Colours every cell of `grid` with `colour`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut colour: impl FnMut(Pos, &T) -> Rgb) -> Self {
+        Self::from_fn(grid.width(), grid.height(), |pos| colour(pos, &grid[pos]))
+    }
+
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Width and height in pixels.
+    pub fn size(&self) -> (usize, usize) {
+        (
+            self.cells.width() * self.scale,
+            self.cells.height() * self.scale,
+        )
+    }
+
+    /// The rows of pixels, as RGB bytes.
+    fn rows(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        self.cells.rows().flat_map(|row| {
+            let pixels: Vec<u8> = row
+                .iter()
+                .flat_map(|c| [c.0, c.1, c.2].repeat(self.scale))
+                .collect();
+            std::iter::repeat_n(pixels, self.scale)
+        })
+    }
+
+    /// Writes a binary PPM (P6).
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = self.size();
+        write!(out, "P6\n{width} {height}\n255\n")?;
+        for row in self.rows() {
+            out.write_all(&row)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_png(&self, out: impl Write) -> Result<()> {
+        let (width, height) = self.size();
+        let mut encoder = png::Encoder::new(out, width.try_into()?, height.try_into()?);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.rows().flatten().collect::<Vec<u8>>())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Writes an SVG with one rectangle per run of equally coloured cells in a row.
+    pub fn write_svg(&self, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = self.size();
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+            self.cells.width(),
+            self.cells.height()
+        )?;
+        for (y, row) in self.cells.rows().enumerate() {
+            let mut x = 0;
+            for run in row.chunk_by(|a, b| a == b) {
+                let Rgb(r, g, b) = run[0];
+                writeln!(
+                    out,
+                    r##"<rect x="{x}" y="{y}" width="{}" height="1" fill="#{r:02x}{g:02x}{b:02x}"/>"##,
+                    run.len()
+                )?;
+                x += run.len();
+            }
+        }
+        writeln!(out, "</svg>")
+    }
+
+    /// Writes the image in the format given by the extension of `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let format = Format::of(path)?;
+        let file =
+            File::create(path).with_context(|| format!("cannot create {}", path.display()))?;
+        let mut out = BufWriter::new(file);
+        match format {
+            Format::Ppm => self.write_ppm(&mut out)?,
+            Format::Png => self.write_png(&mut out)?,
+            Format::Svg => self.write_svg(&mut out)?,
+        }
+        out.flush()
+            .with_context(|| format!("cannot write {}", path.display()))
+    }
+}
+
+/// A day whose puzzle state can be drawn as an image.
+pub trait Pictured: Solution {
+    /// The state after solving the given part.
+    fn image(input: &Self::Input, part: Part, params: &Params) -> Result<Image>;
+}
+
+type ImageFn = fn(&str, Part, &Params) -> Result<Image>;
+
+fn pictured<P: Pictured>(input: &str, part: Part, params: &Params) -> Result<Image> {
+    P::image(&P::parse(input)?, part, params)
+}
+
+static IMAGES: [(u8, ImageFn); 3] = [
+    (8, pictured::<Day8>),
+    (12, pictured::<Day12>),
+    (14, pictured::<Day14>),
+];
+
+/// All days that can be drawn as an image, in ascending order.
+pub fn days() -> impl Iterator<Item = u8> {
+    IMAGES.iter().map(|i| i.0)
+}
+
+/// The image of one part of the given day's puzzle, for the given input.
+pub fn image(day: u8, part: Part, input: &str, params: &Params) -> Result<Image> {
+    let (_, image) = IMAGES
+        .iter()
+        .find(|i| i.0 == day)
+        .with_context(|| format!("no image for day {day}"))?;
+    image(input, part, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkers() -> Image {
+        Image::from_fn(3, 2, |p| match (p.x + p.y) % 2 {
+            0 => Rgb::BLACK,
+            _ => Rgb::WHITE,
+        })
+    }
+
+    #[test]
+    fn ppm() {
+        let mut out = Vec::new();
+        checkers().with_scale(2).write_ppm(&mut out).unwrap();
+        let (header, pixels) = out.split_at(b"P6\n6 4\n255\n".len());
+        assert_eq!(header, b"P6\n6 4\n255\n");
+        assert_eq!(pixels.len(), 6 * 4 * 3);
+        assert_eq!(&pixels[..9], &[0, 0, 0, 0, 0, 0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn png() {
+        let mut out = Vec::new();
+        checkers().write_png(&mut out).unwrap();
+        let decoder = png::Decoder::new(io::Cursor::new(out));
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(&pixels[..6], &[0, 0, 0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn svg() {
+        let mut out = Vec::new();
+        Image::from_fn(3, 1, |p| if p.x < 2 { Rgb::RED } else { Rgb::WHITE })
+            .write_svg(&mut out)
+            .unwrap();
+        let svg = String::from_utf8(out).unwrap();
+        assert!(svg.contains(r##"<rect x="0" y="0" width="2" height="1" fill="#dc1e1e"/>"##));
+        assert!(svg.contains(r##"<rect x="2" y="0" width="1" height="1" fill="#ffffff"/>"##));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(Format::of(Path::new("out.PNG")).unwrap(), Format::Png);
+        assert!(Format::of(Path::new("out.jpg")).is_err());
+    }
+
+    #[test]
+    fn draw_examples() {
+        for day in days() {
+            let input = crate::example(day).unwrap();
+            let params = Params::load(day, "example").unwrap();
+            for part in Part::BOTH {
+                let image = image(day, part, &input, &params).unwrap();
+                let (width, height) = image.size();
+                assert!(width > 0 && height > 0, "day {day} part {part}");
+            }
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+/// Environment variable overriding the directory the puzzle inputs are loaded from.
+/// With the `embed-inputs` feature, setting it loads the inputs from disk instead of
+/// the copies embedded at build time.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const IN_DIR: &str = "inputs";
+
+#[derive(Debug, Error)]
+pub enum InputError {
+    #[error("input file {} not found (set {INPUT_DIR_VAR} to the directory containing the inputs)", .0.display())]
+    NotFound(PathBuf),
+    #[error("input {0} is not valid UTF-8")]
+    NotUtf8(String),
+    #[error("input {0} is empty")]
+    Empty(String),
+    #[error("cannot read input {name}")]
+    Io {
+        name: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// The directory containing the puzzle inputs: `$AOC_INPUT_DIR` if set,
+/// otherwise `inputs` in the crate root (independent of the working directory).
+pub fn input_dir() -> PathBuf {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => dir.into(),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join(IN_DIR),
+    }
+}
+
+/// The example input of the given day.
+pub fn example(day: u8) -> Result<String, InputError> {
+    named(day, "example")
+}
+
+/// The real puzzle input of the given day.
+pub fn input(day: u8) -> Result<String, InputError> {
+    named(day, "real")
+}
+
+/// The path the real puzzle input of the given day is read from.
+pub fn input_path(day: u8) -> PathBuf {
+    named_path(day, "real")
+}
+
+/// The input of the given day with the given name: "example", "real", or the name of
+/// another input, e.g. "example_big" or a team member's "alice". See [`named_path`].
+pub fn named(day: u8, name: &str) -> Result<String, InputError> {
+    #[cfg(feature = "embed-inputs")]
+    if embedded::active() {
+        return embedded::named(day, name);
+    }
+    load(named_path(day, name))
+}
+
+/// The path the named input of the given day is read from: `dayN/<name>.txt` in the
+/// input directory if it exists, otherwise the flat `dayN.txt` for "real" and
+/// `dayN_<name>.txt` for the others (e.g. `day9_example_big.txt`).
+pub fn named_path(day: u8, name: &str) -> PathBuf {
+    path_in(&input_dir(), day, name)
+}
+
+/// The names of all inputs of the given day, in both layouts, sorted.
+pub fn input_names(day: u8) -> Result<Vec<String>, InputError> {
+    #[cfg(feature = "embed-inputs")]
+    if embedded::active() {
+        return Ok(names_of(embedded::files(), day));
+    }
+    names_in(&input_dir(), day)
+}
+
+/// The file of the named input, relative to the input directory, given which files
+/// exist.
+fn relative(day: u8, name: &str, exists: impl Fn(&str) -> bool) -> String {
+    let collected = format!("day{day}/{name}.txt");
+    match name {
+        _ if exists(&collected) => collected,
+        "real" => format!("day{day}.txt"),
+        name => format!("day{day}_{name}.txt"),
+    }
+}
+
+fn path_in(dir: &Path, day: u8, name: &str) -> PathBuf {
+    dir.join(relative(day, name, |file| dir.join(file).is_file()))
+}
+
+/// The names of the inputs of the day among the given files, relative to the input
+/// directory.
+fn names_of(files: impl IntoIterator<Item = String>, day: u8) -> Vec<String> {
+    let (real, flat, collected) = (
+        format!("day{day}.txt"),
+        format!("day{day}_"),
+        format!("day{day}/"),
+    );
+    let mut names: Vec<String> = files
+        .into_iter()
+        .filter_map(|file| {
+            if file == real {
+                return Some("real".to_owned());
+            }
+            let name = file.strip_suffix(".txt")?;
+            let name = name
+                .strip_prefix(&flat)
+                .or_else(|| name.strip_prefix(&collected))?;
+            Some(name.to_owned())
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn names_in(dir: &Path, day: u8) -> Result<Vec<String>, InputError> {
+    let mut files = read_dir(dir)?;
+    for file in read_dir(&dir.join(format!("day{day}")))? {
+        files.push(format!("day{day}/{file}"));
+    }
+    Ok(names_of(files, day))
+}
+
+/// The input files under `inputs/` when the crate was built, see `build.rs`.
+#[cfg(feature = "embed-inputs")]
+mod embedded {
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+    /// The embedded inputs are used unless [`INPUT_DIR_VAR`] is set.
+    pub fn active() -> bool {
+        env::var_os(INPUT_DIR_VAR).is_none()
+    }
+
+    pub fn files() -> impl Iterator<Item = String> {
+        FILES.iter().map(|(file, _)| file.to_string())
+    }
+
+    pub fn named(day: u8, name: &str) -> Result<String, InputError> {
+        let file = relative(day, name, |file| FILES.iter().any(|(f, _)| *f == file));
+        match FILES.iter().find(|(f, _)| *f == file) {
+            Some((_, bytes)) => text(format!("{file} (embedded)"), bytes.to_vec()),
+            None => Err(InputError::NotFound(file.into())),
+        }
+    }
+}
+
+/// The names of the files in a directory, which may not exist.
+fn read_dir(dir: &Path) -> Result<Vec<String>, InputError> {
+    let io_error = |source| InputError::Io {
+        name: dir.display().to_string(),
+        source,
+    };
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_error(e)),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(io_error)?;
+        if entry.path().is_file() {
+            names.extend(entry.file_name().into_string());
+        }
+    }
+    Ok(names)
+}
+
+/// Load an input file, or read the input from stdin if the path is `-`.
+pub fn load(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+    let (name, bytes) = if path == Path::new("-") {
+        let name = "<stdin>".to_owned();
+        let mut bytes = Vec::new();
+        match io::stdin().read_to_end(&mut bytes) {
+            Ok(_) => (name, bytes),
+            Err(source) => return Err(InputError::Io { name, source }),
+        }
+    } else {
+        let name = path.display().to_string();
+        match fs::read(path) {
+            Ok(bytes) => (name, bytes),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(InputError::NotFound(path.to_owned()))
+            }
+            Err(source) => return Err(InputError::Io { name, source }),
+        }
+    };
+    text(name, bytes)
+}
+
+/// The input as text, if it is valid.
+fn text(name: String, bytes: Vec<u8>) -> Result<String, InputError> {
+    let text = String::from_utf8(bytes).map_err(|_| InputError::NotUtf8(name.clone()))?;
+    if text.trim().is_empty() {
+        return Err(InputError::Empty(name));
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-2022-{}-{name}", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn load_file() {
+        let path = temp_file("valid.txt", b"1000\n2000\n");
+        assert_eq!(load(&path).unwrap(), "1000\n2000\n");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn load_errors() {
+        let missing = env::temp_dir().join("aoc-2022-does-not-exist.txt");
+        assert!(matches!(load(missing), Err(InputError::NotFound(_))));
+
+        let empty = temp_file("empty.txt", b" \n\n");
+        assert!(matches!(load(&empty), Err(InputError::Empty(_))));
+        fs::remove_file(empty).unwrap();
+
+        let binary = temp_file("binary.txt", &[0xff, 0xfe, 0x00]);
+        assert!(matches!(load(&binary), Err(InputError::NotUtf8(_))));
+        fs::remove_file(binary).unwrap();
+    }
+
+    #[test]
+    fn inputs_found_from_crate_root() {
+        assert!(example(1).is_ok());
+        assert!(input_names(9).unwrap().contains(&"example_big".to_owned()));
+    }
+
+    #[test]
+    fn both_layouts() {
+        let dir = env::temp_dir().join(format!("aoc-2022-layouts-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("day1")).unwrap();
+        for file in [
+            "day1.txt",
+            "day1_example.txt",
+            "day10_example.txt",
+            "notes.md",
+        ] {
+            fs::write(dir.join(file), "1\n").unwrap();
+        }
+        for file in ["alice.txt", "example.txt"] {
+            fs::write(dir.join("day1").join(file), "2\n").unwrap();
+        }
+
+        assert_eq!(names_in(&dir, 1).unwrap(), ["alice", "example", "real"]);
+        assert_eq!(names_in(&dir, 10).unwrap(), ["example"]);
+        assert!(names_in(&dir, 2).unwrap().is_empty());
+        // the collection takes precedence
+        assert_eq!(path_in(&dir, 1, "example"), dir.join("day1/example.txt"));
+        assert_eq!(path_in(&dir, 1, "real"), dir.join("day1.txt"));
+        assert_eq!(path_in(&dir, 10, "example"), dir.join("day10_example.txt"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "embed-inputs")]
+    #[test]
+    fn embedded_inputs() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(IN_DIR);
+        assert_eq!(
+            embedded::named(9, "example_big").unwrap(),
+            fs::read_to_string(dir.join("day9_example_big.txt")).unwrap()
+        );
+        assert_eq!(names_of(embedded::files(), 9), names_in(&dir, 9).unwrap());
+        assert!(matches!(
+            embedded::named(9, "missing"),
+            Err(InputError::NotFound(_))
+        ));
+    }
+}
@@ -1,11 +1,97 @@
-use std::fs;
+use anyhow::{Context, Result};
 
-const IN_DIR: &str = "inputs";
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+mod inputs;
+mod solution;
 
-pub fn example(day: u8) -> String {
-    fs::read_to_string(format!("{IN_DIR}/day{day}_example.txt")).unwrap()
+pub mod animate;
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod client;
+pub mod debugger;
+pub mod differential;
+pub mod generate;
+pub mod geom;
+pub mod grid;
+pub mod image;
+pub mod logging;
+pub mod params;
+pub mod parse;
+pub mod runner;
+pub mod scaffold;
+pub mod serve;
+pub mod snapshot;
+pub mod submit;
+
+pub use inputs::{
+    example, input, input_dir, input_names, input_path, load, named, named_path, InputError,
+    INPUT_DIR_VAR,
+};
+pub use params::Params;
+pub use parse::ParseError;
+pub use solution::{Part, Solution, Solver};
+
+/// All solvers, in ascending order of their day.
+static SOLVERS: [&dyn Solver; 16] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+];
+
+/// All days that have a solver, in ascending order.
+pub fn days() -> impl Iterator<Item = u8> {
+    SOLVERS.iter().map(|s| s.day())
+}
+
+/// The solver for the given day, if there is one.
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    SOLVERS.iter().find(|s| s.day() == day).copied()
+}
+
+/// Solve one part of the given day's puzzle for the given input and its parameters.
+pub fn solve(day: u8, part: Part, input: &str, params: &Params) -> Result<String> {
+    solver(day)
+        .with_context(|| format!("no solver for day {day}"))?
+        .run(input, part, params)
 }
 
-pub fn input(day: u8) -> String {
-    fs::read_to_string(format!("{IN_DIR}/day{day}.txt")).unwrap()
+/// Prints the answers to both parts of a day's puzzle, for the example and the
+/// real input: what each of the `dayN` binaries does.
+pub fn print_answers<S: Solution>() -> Result<()> {
+    for (name, input) in [("example", example(S::DAY)?), ("real", input(S::DAY)?)] {
+        let params = Params::load(S::DAY, name)?;
+        let parsed = S::parse(&input)?;
+        println!("{}", S::part1(&parsed, &params)?);
+        println!("{}", S::part2(&parsed, &params)?);
+    }
+    Ok(())
 }
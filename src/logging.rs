@@ -0,0 +1,90 @@
+//! Diagnostics on stderr, filtered by level, so that stdout carries the answers alone.
+//!
+//! The solvers log through the [`log`] macros: `debug` for intermediate results
+//! and `trace` for whole puzzle states.
+
+use std::{env, io::Write};
+
+use anyhow::{anyhow, Context, Result};
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// The environment variable selecting the level when no `-v`/`-q` flag is given:
+/// `off`, `error`, `warn`, `info`, `debug` or `trace`.
+pub const LOG_VAR: &str = "AOC_LOG";
+
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+struct Stderr;
+
+impl Log for Stderr {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        // `aoc_2022::day16` is logged as `day16`
+        let target = record.target();
+        let target = target.rsplit("::").next().unwrap_or(target);
+        let _ = writeln!(
+            std::io::stderr().lock(),
+            "{:>5} {target}: {}",
+            record.level(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
+
+static LOGGER: Stderr = Stderr;
+
+/// The level chosen by the command line flags: every `-v` shows one more level
+/// than the default, `-q` shows errors only. Without flags, it is up to [`LOG_VAR`].
+pub fn level(verbose: u8, quiet: bool, var: Option<&str>) -> Result<LevelFilter> {
+    if quiet {
+        return Ok(LevelFilter::Error);
+    }
+    if verbose > 0 {
+        let levels = LevelFilter::iter().collect::<Vec<_>>();
+        let default = levels.iter().position(|&l| l == DEFAULT_LEVEL).unwrap();
+        let index = (default + usize::from(verbose)).min(levels.len() - 1);
+        return Ok(levels[index]);
+    }
+    match var {
+        Some(var) => var
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("invalid ${LOG_VAR}: {var}"))
+            .context("expected off, error, warn, info, debug or trace"),
+        None => Ok(DEFAULT_LEVEL),
+    }
+}
+
+/// Log to stderr at the level chosen by the flags or the environment.
+pub fn init(verbose: u8, quiet: bool) -> Result<()> {
+    let var = env::var(LOG_VAR).ok();
+    let level = level(verbose, quiet, var.as_deref())?;
+    log::set_logger(&LOGGER).map_err(|e| anyhow!("cannot install the logger: {e}"))?;
+    log::set_max_level(level);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        assert_eq!(level(0, false, None).unwrap(), LevelFilter::Info);
+        assert_eq!(level(1, false, None).unwrap(), LevelFilter::Debug);
+        assert_eq!(level(5, false, Some("off")).unwrap(), LevelFilter::Trace);
+        assert_eq!(level(0, true, Some("trace")).unwrap(), LevelFilter::Error);
+        assert_eq!(level(0, false, Some("WARN")).unwrap(), LevelFilter::Warn);
+        assert!(level(0, false, Some("loud")).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//! Puzzle parameters that differ between the example and the real input, or that a
+//! puzzle variant might change: the row day 15 scans, the minutes of day 16, …
+//!
+//! They are checked in as `aoc.toml`: a `[dayN]` table per day, with subtables
+//! `[dayN.<input name>]` overriding them for one input (see [`crate::named`]). On the
+//! command line, `--param key=value` (or `dayN.key=value`) overrides both.
+
+use std::{collections::BTreeMap, fmt, fs, path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::DeserializeOwned;
+use toml::{Table, Value};
+
+use crate::cancel::Cancel;
+
+const PARAMS_FILE: &str = "aoc.toml";
+
+/// The parameters of one day, for one input, and the token cancelling the solver.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    day: u8,
+    values: BTreeMap<String, Value>,
+    cancel: Cancel,
+}
+
+impl Params {
+    /// No parameters at all.
+    pub fn new(day: u8) -> Self {
+        Params {
+            day,
+            ..Params::default()
+        }
+    }
+
+    /// The parameters of the given day and input, from the checked-in `aoc.toml`.
+    pub fn load(day: u8, input: &str) -> Result<Self> {
+        Ok(Config::load()?.params(day, input))
+    }
+
+    pub fn with(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.values.insert(key.to_owned(), value.into());
+        self
+    }
+
+    /// Cancel the solver with the given token, e.g. after a timeout.
+    pub fn with_cancel(mut self, cancel: Cancel) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// The token the solver's long-running loops poll.
+    pub fn cancel(&self) -> &Cancel {
+        &self.cancel
+    }
+
+    /// Whether the day has a parameter of this name.
+    pub fn has(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Applies the overrides meant for this day: those naming the day, and those
+    /// without a day naming one of its parameters.
+    pub fn apply(&mut self, overrides: &[Override]) {
+        for o in overrides {
+            let applies = match o.day {
+                Some(day) => day == self.day,
+                None => self.has(&o.key),
+            };
+            if applies {
+                self.values.insert(o.key.clone(), o.value.clone());
+            }
+        }
+    }
+
+    /// The value of a parameter, e.g. an integer, a string or an array of them.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<T> {
+        let value = self.values.get(key).with_context(|| {
+            format!(
+                "day {} has no parameter {key} (set it in {PARAMS_FILE} or with --param {key}=…)",
+                self.day
+            )
+        })?;
+        value
+            .clone()
+            .try_into()
+            .with_context(|| format!("invalid day {} parameter {key} = {value}", self.day))
+    }
+}
+
+/// A parameter set on the command line: `key=value`, or `dayN.key=value` to only
+/// set it for one day. Values are TOML (`10`, `[20, 60]`, `"[[2]]"`); anything else
+/// is taken as a string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Override {
+    pub day: Option<u8>,
+    pub key: String,
+    pub value: Value,
+}
+
+impl FromStr for Override {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected key=value, found {s}"))?;
+        let (day, key) = match key.trim().split_once('.') {
+            Some((day, key)) => {
+                let day = day
+                    .strip_prefix("day")
+                    .and_then(|d| d.parse().ok())
+                    .with_context(|| format!("expected dayN.{key}, found {day}.{key}"))?;
+                (Some(day), key)
+            }
+            None => (None, key.trim()),
+        };
+        if key.is_empty() {
+            bail!("the parameter name is missing in {s}");
+        }
+        let value = value.trim();
+        let value = toml::from_str::<Table>(&format!("value = {value}"))
+            .ok()
+            .and_then(|mut t| t.remove("value"))
+            .unwrap_or_else(|| Value::String(value.to_owned()));
+        Ok(Override {
+            day,
+            key: key.to_owned(),
+            value,
+        })
+    }
+}
+
+impl fmt::Display for Override {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day{day}.")?;
+        }
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+/// The parameters of all days, as read from `aoc.toml`.
+#[derive(Debug, Default)]
+pub struct Config(BTreeMap<u8, Table>);
+
+impl Config {
+    /// The path of the parameters file in the crate root.
+    pub fn path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(PARAMS_FILE)
+    }
+
+    /// Load the checked-in parameters file (with the `embed-inputs` feature, the copy
+    /// embedded at build time if it is not found).
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        #[cfg(feature = "embed-inputs")]
+        if !path.exists() {
+            return Self::parse(include_str!("../aoc.toml"));
+        }
+        let text =
+            fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("invalid parameters file {}", path.display()))
+    }
+
+    /// Parse a parameters file: tables `[dayN]` of parameters, with subtables
+    /// `[dayN.<input name>]` overriding them.
+    pub fn parse(text: &str) -> Result<Self> {
+        let tables: BTreeMap<String, Table> = toml::from_str(text)?;
+        let mut days = BTreeMap::new();
+        for (key, table) in tables {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .with_context(|| format!("expected a table named dayN, found {key}"))?;
+            days.insert(day, table);
+        }
+        Ok(Config(days))
+    }
+
+    /// The parameters of the given day for the named input.
+    pub fn params(&self, day: u8, input: &str) -> Params {
+        let mut params = Params::new(day);
+        let Some(table) = self.0.get(&day) else {
+            return params;
+        };
+        let (inputs, values): (Vec<_>, Vec<_>) =
+            table.iter().partition(|(_, value)| value.is_table());
+        for (key, value) in values {
+            params.values.insert(key.clone(), value.clone());
+        }
+        for (name, overrides) in inputs {
+            if let (true, Value::Table(overrides)) = (name == input, overrides) {
+                for (key, value) in overrides {
+                    params.values.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "\
+[day15]
+part1_row = 2000000
+part2_bound = 4000000
+
+[day15.example]
+part1_row = 10
+part2_bound = 20
+
+[day13]
+dividers = [\"[[2]]\", \"[[6]]\"]
+";
+
+    #[test]
+    fn per_input_overrides() {
+        let config = Config::parse(CONFIG).unwrap();
+        let example = config.params(15, "example");
+        assert_eq!(example.get::<i32>("part1_row").unwrap(), 10);
+        let real = config.params(15, "real");
+        assert_eq!(real.get::<i32>("part1_row").unwrap(), 2000000);
+        assert!(!real.has("example"));
+        let dividers: Vec<String> = config.params(13, "example").get("dividers").unwrap();
+        assert_eq!(dividers, ["[[2]]", "[[6]]"]);
+
+        let err = real.get::<i32>("rows").unwrap_err().to_string();
+        assert!(err.starts_with("day 15 has no parameter rows"), "{err}");
+        assert!(real.get::<String>("part1_row").is_err());
+        assert!(Config::parse("[dayX]\nrows = 1\n").is_err());
+    }
+
+    #[test]
+    fn command_line_overrides() {
+        let config = Config::parse(CONFIG).unwrap();
+        let overrides: Vec<Override> = ["part1_row=11", "day13.dividers=[\"[[1]]\"]", "name=abc"]
+            .iter()
+            .map(|o| o.parse().unwrap())
+            .collect();
+        assert_eq!(overrides[2].value, Value::String("abc".into()));
+        assert_eq!(overrides[1].to_string(), "day13.dividers=[\"[[1]]\"]");
+
+        let mut day15 = config.params(15, "example");
+        day15.apply(&overrides);
+        assert_eq!(day15.get::<i32>("part1_row").unwrap(), 11);
+        assert!(!day15.has("dividers") && !day15.has("name"));
+        let mut day13 = config.params(13, "real");
+        day13.apply(&overrides);
+        assert_eq!(day13.get::<Vec<String>>("dividers").unwrap(), ["[[1]]"]);
+
+        assert!("part1_row".parse::<Override>().is_err());
+        assert!("days.rows=1".parse::<Override>().is_err());
+        assert!("=1".parse::<Override>().is_err());
+    }
+}
@@ -0,0 +1,166 @@
+//! Location-aware errors for the puzzle input parsers.
+
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+/// A malformed puzzle input: what is wrong, and where.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, in characters
+    pub column: usize,
+    /// The offending line.
+    pub text: String,
+    /// The number of characters to underline (at least one).
+    pub width: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error about something missing at the end of the input.
+    pub fn end_of_input(day: u8, input: &str, message: impl fmt::Display) -> Self {
+        match lines(day, input).last() {
+            Some(last) => last.error(last.end(), message),
+            None => ParseError {
+                day,
+                line: 1,
+                column: 1,
+                text: String::new(),
+                width: 1,
+                message: message.to_string(),
+            },
+        }
+    }
+}
+
+/// Renders the location and the line, with a caret under the problem:
+///
+/// ```text
+/// day 4, line 2, column 3: invalid digit found in string
+///  2 | 2-x,6-8
+///    |   ^
+/// ```
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+        writeln!(f, " {} | {}", self.line, self.text)?;
+        write!(
+            f,
+            " {gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width)
+        )
+    }
+}
+
+/// One line of a puzzle input, which knows its position for error reporting.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    day: u8,
+    number: usize,
+    text: &'a str,
+}
+
+/// The lines of the given day's input.
+pub fn lines(day: u8, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(i, text)| Line {
+        day,
+        number: i + 1,
+        text,
+    })
+}
+
+impl<'a> Line<'a> {
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// 1-based
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// The empty string at the end of the line, to report something missing.
+    pub fn end(&self) -> &'a str {
+        &self.text[self.text.len()..]
+    }
+
+    /// An error underlining `at`, which should be a slice of this line's text
+    /// (otherwise the whole line is underlined).
+    pub fn error(&self, at: &str, message: impl fmt::Display) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize).wrapping_sub(start);
+        let (column, width) = if offset <= self.text.len() && offset + at.len() <= self.text.len() {
+            (self.text[..offset].chars().count() + 1, at.chars().count())
+        } else {
+            (1, self.text.chars().count())
+        };
+        ParseError {
+            day: self.day,
+            line: self.number,
+            column,
+            text: self.text.to_owned(),
+            width: width.max(1),
+            message: message.to_string(),
+        }
+    }
+
+    /// Parse `at` (a slice of this line), reporting failures at its position.
+    pub fn parse<T>(&self, at: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        at.parse()
+            .map_err(|e: T::Err| self.error(at, format!("{:#}", e.into())))
+    }
+
+    /// Split `at` (a slice of this line) around the first `delim`.
+    pub fn split_once(&self, at: &'a str, delim: &str) -> Result<(&'a str, &'a str), ParseError> {
+        at.split_once(delim).ok_or_else(|| {
+            let end = &at[at.len()..];
+            self.error(end, format!("expected {delim:?}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn underline_the_problem() {
+        let line = lines(4, "2-4,6-8\n2-x,6-8\n").nth(1).unwrap();
+        let (elf, _) = line.split_once(line.text(), ",").unwrap();
+        let (_, upper) = line.split_once(elf, "-").unwrap();
+        let err = line.parse::<u32>(upper).unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (4, 2, 3));
+        assert_eq!(
+            err.to_string(),
+            "day 4, line 2, column 3: invalid digit found in string\n 2 | 2-x,6-8\n   |   ^"
+        );
+    }
+
+    #[test]
+    fn point_at_missing_parts() {
+        let line = lines(9, "R 4\nU").nth(1).unwrap();
+        let err = line.split_once(line.text(), " ").unwrap_err();
+        assert_eq!((err.line, err.column, err.width), (2, 2, 1));
+        assert_eq!(err.message, "expected \" \"");
+
+        let err = ParseError::end_of_input(5, "a\nbc\n", "missing orders");
+        assert_eq!((err.line, err.column), (2, 3));
+
+        // slices of other strings underline the whole line
+        let err = line.error("elsewhere", "oops");
+        assert_eq!((err.column, err.width), (1, 1));
+    }
+}
@@ -0,0 +1,309 @@
+//! Running many parts at once on a pool of threads, where one failing (or panicking,
+//! or timing out) part does not stop the others, and summarising the results as a
+//! table.
+
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+
+use crate::{
+    answers::Answers,
+    cancel::{self, Cancel},
+    Part,
+};
+
+/// The answer, or what went wrong (including panics).
+pub(crate) fn catch(solve: impl FnOnce() -> Result<String>) -> Result<String, String> {
+    match panic::catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(answer) => answer.map_err(|e| format!("{e:#}")),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("panicked: {message}"))
+        }
+    }
+}
+
+/// How one part went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub day: u8,
+    /// The name of the input, see [`crate::named`].
+    pub input: String,
+    pub part: Part,
+    pub answer: Result<String, String>,
+    /// Whether the solver gave up because it ran out of time.
+    pub timed_out: bool,
+    pub time: Duration,
+    /// The known answer, if there is one.
+    pub expected: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong,
+    Failed,
+    TimedOut,
+    /// Solved, but the answer is not known.
+    Unchecked,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Status::Correct => "ok",
+            Status::Wrong => "WRONG",
+            Status::Failed => "FAILED",
+            Status::TimedOut => "TIMEOUT",
+            Status::Unchecked => "-",
+        })
+    }
+}
+
+impl Run {
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) if self.timed_out => Status::TimedOut,
+            (Err(_), _) => Status::Failed,
+            (Ok(_), None) => Status::Unchecked,
+            (Ok(answer), Some(expected)) if answer == expected => Status::Correct,
+            (Ok(_), Some(_)) => Status::Wrong,
+        }
+    }
+
+    /// The answer (or error) on a single line.
+    fn summary(&self) -> String {
+        match &self.answer {
+            Ok(answer) if answer.contains('\n') => format!("({} lines)", answer.lines().count()),
+            Ok(answer) => answer.clone(),
+            Err(e) => e.lines().next().unwrap_or_default().to_owned(),
+        }
+    }
+}
+
+/// Solves the given parts of the days' named inputs on `jobs` threads. The results are
+/// in the order of `tasks`. Each part gets a token that is cancelled after the timeout,
+/// to pass on to its solver.
+pub fn run_all(
+    tasks: &[(u8, String, Part)],
+    jobs: usize,
+    timeout: Option<Duration>,
+    solve: impl Fn(u8, &str, Part, &Cancel) -> Result<String> + Sync,
+) -> Vec<Run> {
+    let next = AtomicUsize::new(0);
+    let runs = Mutex::new(Vec::with_capacity(tasks.len()));
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, tasks.len().max(1)) {
+            scope.spawn(|| {
+                while let Some((day, input, part)) = tasks.get(next.fetch_add(1, Ordering::Relaxed))
+                {
+                    let start = Instant::now();
+                    let cancel = Cancel::new(timeout);
+                    let mut timed_out = false;
+                    let answer = catch(|| {
+                        solve(*day, input, *part, &cancel)
+                            .inspect_err(|e| timed_out = cancel::is_cancelled(e))
+                    });
+                    let answer = match (answer, timeout) {
+                        (Err(_), Some(timeout)) if timed_out => {
+                            Err(format!("timed out after {timeout:?}"))
+                        }
+                        (answer, _) => answer,
+                    };
+                    let run = Run {
+                        day: *day,
+                        input: input.clone(),
+                        part: *part,
+                        answer,
+                        timed_out,
+                        time: start.elapsed(),
+                        expected: None,
+                    };
+                    runs.lock().unwrap().push(run);
+                }
+            });
+        }
+    });
+    let mut runs = runs.into_inner().unwrap();
+    let position = |run: &Run| {
+        tasks
+            .iter()
+            .position(|t| (t.0, &t.1, t.2) == (run.day, &run.input, run.part))
+    };
+    runs.sort_by_key(position);
+    runs
+}
+
+/// Fills in the known answers for the runs' inputs.
+pub fn check(runs: &mut [Run], answers: &Answers) {
+    for run in runs {
+        run.expected = answers
+            .get(run.day, &run.input, run.part)
+            .map(str::to_owned);
+    }
+}
+
+/// Whether the runs are on more than one input, which the tables then show.
+fn several_inputs(runs: &[Run]) -> bool {
+    runs.iter().any(|r| r.input != runs[0].input)
+}
+
+/// A plain text table of the runs, with aligned columns.
+pub fn table(runs: &[Run]) -> String {
+    let column = |header: &str, cell: &dyn Fn(&Run) -> String| {
+        runs.iter()
+            .map(|r| cell(r).len())
+            .chain([header.len()])
+            .max()
+            .unwrap_or_default()
+    };
+    let width = column("answer", &Run::summary);
+    let inputs = match several_inputs(runs) {
+        true => column("input", &|r| r.input.clone()),
+        false => 0,
+    };
+    let input_cell = |input: &str| match inputs {
+        0 => String::new(),
+        _ => format!("  {input:<inputs$}"),
+    };
+    let mut table = format!(
+        "day part{}  {:<width$} {:>12}  status\n",
+        input_cell("input"),
+        "answer",
+        "time"
+    );
+    for run in runs {
+        table += &format!(
+            "{:>3} {:>4}{}  {:<width$} {:>12}  {}\n",
+            run.day,
+            run.part.number(),
+            input_cell(&run.input),
+            run.summary(),
+            format!("{:.2?}", run.time),
+            run.status()
+        );
+    }
+    table
+}
+
+/// The runs as a Markdown table, e.g. for a README.
+pub fn markdown(runs: &[Run]) -> String {
+    let inputs = several_inputs(runs);
+    let (header, rule) = match inputs {
+        true => ("| Input ", "|-------"),
+        false => ("", ""),
+    };
+    let mut table = format!("| Day | Part {header}| Answer | Time | Status |\n");
+    table += &format!("|----:|-----:{rule}|--------|-----:|--------|\n");
+    for run in runs {
+        let input = match inputs {
+            true => format!("| {} ", run.input),
+            false => String::new(),
+        };
+        table += &format!(
+            "| {} | {} {input}| `{}` | {:.2?} | {} |\n",
+            run.day,
+            run.part,
+            run.summary().replace('|', "\\|"),
+            run.time,
+            run.status()
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::bail;
+
+    use super::*;
+
+    fn solve(day: u8, _input: &str, part: Part, cancel: &Cancel) -> Result<String> {
+        match (day, part) {
+            (1, _) => Ok(format!("{day}.{part}")),
+            (2, Part::One) => bail!("no input\nmore context"),
+            (2, Part::Two) => panic!("index out of bounds"),
+            (4, _) => loop {
+                cancel.check()?;
+            },
+            _ => Ok("#.\n.#".to_owned()),
+        }
+    }
+
+    #[test]
+    fn isolate_failures() {
+        let tasks: Vec<(u8, String, Part)> = (1..=3)
+            .flat_map(|day| Part::BOTH.map(|part| (day, "real".to_owned(), part)))
+            .collect();
+        let mut runs = run_all(&tasks, 4, None, solve);
+        let order: Vec<_> = runs
+            .iter()
+            .map(|r| (r.day, r.input.clone(), r.part))
+            .collect();
+        assert_eq!(order, tasks);
+        assert_eq!(
+            runs[3].answer,
+            Err("panicked: index out of bounds".to_owned())
+        );
+
+        let answers = Answers::parse("[day1.real]\npart1 = \"1.1\"\npart2 = \"1.3\"\n").unwrap();
+        check(&mut runs, &answers);
+        let status: Vec<Status> = runs.iter().map(Run::status).collect();
+        use Status::*;
+        assert_eq!(
+            status,
+            [Correct, Wrong, Failed, Failed, Unchecked, Unchecked]
+        );
+
+        let table = table(&runs);
+        assert!(table.contains("  2    1  no input "), "{table}");
+        assert!(table.contains("  3    2  (2 lines) "), "{table}");
+        let markdown = markdown(&runs);
+        assert_eq!(markdown.lines().count(), 2 + tasks.len());
+        assert!(markdown.contains("| 1 | 2 | `1.2` |"), "{markdown}");
+    }
+
+    #[test]
+    fn input_column() {
+        let tasks: Vec<(u8, String, Part)> = ["alice", "bob", "example"]
+            .map(|input| (1, input.to_owned(), Part::One))
+            .into();
+        let mut runs = run_all(&tasks, 2, None, |_, input, _, _| {
+            Ok(input.len().to_string())
+        });
+        let answers = Answers::parse("[day1.alice]\npart1 = 5\n[day1.bob]\npart1 = 5\n").unwrap();
+        check(&mut runs, &answers);
+        let status: Vec<Status> = runs.iter().map(Run::status).collect();
+        assert_eq!(status, [Status::Correct, Status::Wrong, Status::Unchecked]);
+
+        let table = table(&runs);
+        assert!(table.starts_with("day part  input    answer"), "{table}");
+        assert!(table.contains("  1    1  bob      3 "), "{table}");
+        let markdown = markdown(&runs);
+        assert!(markdown.contains("| 1 | 1 | alice | `5` |"), "{markdown}");
+    }
+
+    #[test]
+    fn time_out() {
+        let tasks: Vec<(u8, String, Part)> =
+            [3, 4].map(|day| (day, "real".to_owned(), Part::One)).into();
+        let runs = run_all(&tasks, 2, Some(Duration::from_millis(10)), solve);
+        let status: Vec<Status> = runs.iter().map(Run::status).collect();
+        assert_eq!(status, [Status::Unchecked, Status::TimedOut]);
+        assert_eq!(runs[1].answer, Err("timed out after 10ms".to_owned()));
+        assert!(table(&runs).contains("timed out after 10ms"));
+    }
+}
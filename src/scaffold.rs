@@ -0,0 +1,294 @@
+//! Scaffolding for a new day: the solver skeleton with its tests, the binary, empty
+//! input files, an answers entry, and the registration in the library.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+const SOLVER: &str = r#"//! Day {day}.
+
+use std::fmt::Display;
+
+use anyhow::{anyhow, Result};
+
+use crate::{parse, Params, ParseError, Solution};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u8 = {day};
+    /// The lines of the puzzle input.
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse::lines(Day{day}::DAY, input)
+            .map(|ll| ll.text().to_owned())
+            .collect())
+    }
+
+    fn part1(_lines: &Self::Input, _params: &Params) -> Result<impl Display> {
+        Err::<u64, _>(anyhow!("part 1 is not solved yet"))
+    }
+
+    fn part2(_lines: &Self::Input, _params: &Params) -> Result<impl Display> {
+        Err::<u64, _>(anyhow!("part 2 is not solved yet"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the answers to the example, from the puzzle text
+    const EXAMPLE_PART1: &str = "";
+    const EXAMPLE_PART2: &str = "";
+
+    #[test]
+    fn part1_example() {
+        let example = Day{day}::parse(&crate::example({day}).unwrap()).unwrap();
+        let params = Params::load({day}, "example").unwrap();
+        let answer = Day{day}::part1(&example, &params).unwrap();
+        assert_eq!(answer.to_string(), EXAMPLE_PART1);
+    }
+
+    #[test]
+    fn part2_example() {
+        let example = Day{day}::parse(&crate::example({day}).unwrap()).unwrap();
+        let params = Params::load({day}, "example").unwrap();
+        let answer = Day{day}::part2(&example, &params).unwrap();
+        assert_eq!(answer.to_string(), EXAMPLE_PART2);
+    }
+}
+"#;
+
+const BINARY: &str = r#"use aoc_2022::day{day}::Day{day};
+
+fn main() -> anyhow::Result<()> {
+    aoc_2022::print_answers::<Day{day}>()
+}
+"#;
+
+const ANSWERS: &str = r#"
+[day{day}.example]
+# part1 =
+# part2 =
+"#;
+
+/// The files of the crate that make up the days.
+pub struct Project {
+    root: PathBuf,
+    inputs: PathBuf,
+}
+
+impl Project {
+    /// This crate, with the inputs in [`crate::input_dir`].
+    pub fn current() -> Self {
+        Project {
+            root: PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+            inputs: crate::input_dir(),
+        }
+    }
+
+    /// The crate in `root`, with the inputs in `root/inputs`.
+    pub fn at(root: &Path) -> Self {
+        Project {
+            root: root.to_owned(),
+            inputs: root.join("inputs"),
+        }
+    }
+
+    /// Adds the given day. Nothing is written if any of the day's source files or
+    /// answers exist already; existing input files (e.g. from `aoc fetch`) are kept.
+    /// Returns the files that were created or changed.
+    pub fn new_day(&self, day: u8) -> Result<Vec<PathBuf>> {
+        let solver = self.root.join(format!("src/day{day}.rs"));
+        let binary = self.root.join(format!("src/bin/day{day}.rs"));
+        for path in [&solver, &binary] {
+            if path.exists() {
+                bail!("day {day} exists already: {}", path.display());
+            }
+        }
+        let answers_path = self.root.join("answers.toml");
+        let answers = read(&answers_path)?;
+        if answers
+            .lines()
+            .any(|l| l.starts_with(&format!("[day{day}.")))
+        {
+            bail!(
+                "{} has answers for day {day} already",
+                answers_path.display()
+            );
+        }
+        let lib_path = self.root.join("src/lib.rs");
+        let lib = read(&lib_path)?;
+        let lib =
+            register(&lib, day).with_context(|| format!("cannot edit {}", lib_path.display()))?;
+
+        let fill = |template: &str| template.replace("{day}", &day.to_string());
+        let mut changed = Vec::new();
+        for (path, text) in [(solver, fill(SOLVER)), (binary, fill(BINARY))] {
+            create(&path, &text)?;
+            changed.push(path);
+        }
+        fs::create_dir_all(&self.inputs)
+            .with_context(|| format!("cannot create {}", self.inputs.display()))?;
+        for name in [format!("day{day}.txt"), format!("day{day}_example.txt")] {
+            let path = self.inputs.join(name);
+            match create(&path, "") {
+                Ok(()) => changed.push(path),
+                Err(_) if path.exists() => {}
+                Err(e) => return Err(e),
+            }
+        }
+        write(&answers_path, &(answers + &fill(ANSWERS)))?;
+        changed.push(answers_path);
+        write(&lib_path, &lib)?;
+        changed.push(lib_path);
+        Ok(changed)
+    }
+}
+
+/// Adds the module and the solver of the day to the library source.
+fn register(lib: &str, day: u8) -> Result<String> {
+    let module = format!("day{day}");
+    let mut lines: Vec<String> = lib.lines().map(str::to_owned).collect();
+
+    let modules: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| {
+            let name = l.strip_prefix("pub mod ")?.strip_suffix(';')?;
+            name.strip_prefix("day")?.parse::<u8>().ok()?;
+            Some((i, name))
+        })
+        .collect();
+    if modules.iter().any(|&(_, name)| name == module) {
+        bail!("day {day} is declared already");
+    }
+    let Some(&(last, _)) = modules.last() else {
+        bail!("no day modules found");
+    };
+    // rustfmt sorts the declarations as strings
+    let at = modules
+        .iter()
+        .find(|&&(_, name)| name > module.as_str())
+        .map_or(last + 1, |&(i, _)| i);
+    lines.insert(at, format!("pub mod {module};"));
+
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("static SOLVERS: [&dyn Solver; "))
+        .context("no SOLVERS table found")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l == "];")
+            .context("SOLVERS table not closed")?;
+    let solver_day = |l: &str| -> Option<u8> {
+        l.trim()
+            .strip_prefix("&day")?
+            .split("::")
+            .next()?
+            .parse()
+            .ok()
+    };
+    let at = (start + 1..end)
+        .find(|&i| solver_day(&lines[i]).is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(at, format!("    &{module}::Day{day},"));
+    let count = (start + 1..end + 1)
+        .filter(|&i| solver_day(&lines[i]).is_some())
+        .count();
+    lines[start] = format!("static SOLVERS: [&dyn Solver; {count}] = [");
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))
+}
+
+fn write(path: &Path, text: &str) -> Result<()> {
+    fs::write(path, text).with_context(|| format!("cannot write {}", path.display()))
+}
+
+/// Writes a new file, failing if it exists.
+fn create(path: &Path, text: &str) -> Result<()> {
+    let mut file = match OpenOptions::new().write(true).create_new(true).open(path) {
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            bail!("{} exists already", path.display())
+        }
+        file => file.with_context(|| format!("cannot create {}", path.display()))?,
+    };
+    file.write_all(text.as_bytes())
+        .with_context(|| format!("cannot write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const LIB: &str = "mod inputs;\npub mod day1;\npub mod day2;\npub mod grid;\n\n\
+        static SOLVERS: [&dyn Solver; 2] = [\n    &day1::Day1,\n    &day2::Day2,\n];\n";
+
+    fn project(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-2022-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("answers.toml"), "[day1.example]\npart1 = 1\n").unwrap();
+        root
+    }
+
+    #[test]
+    fn register_in_order() {
+        let lib = register(LIB, 10).unwrap();
+        assert!(lib.contains("pub mod day1;\npub mod day10;\npub mod day2;\n"));
+        assert!(lib.contains(
+            "[&dyn Solver; 3] = [\n    &day1::Day1,\n    &day2::Day2,\n    &day10::Day10,\n];"
+        ));
+        assert!(register(&lib, 10).is_err());
+    }
+
+    #[test]
+    fn new_day() {
+        let root = project("scaffold");
+        fs::create_dir_all(root.join("inputs")).unwrap();
+        fs::write(root.join("inputs/day3.txt"), "fetched\n").unwrap();
+
+        let project = Project::at(&root);
+        let changed = project.new_day(3).unwrap();
+        assert_eq!(changed.len(), 5);
+        let solver = fs::read_to_string(root.join("src/day3.rs")).unwrap();
+        assert!(solver.contains("impl Solution for Day3 {\n    const DAY: u8 = 3;"));
+        assert_eq!(
+            fs::read_to_string(root.join("inputs/day3.txt")).unwrap(),
+            "fetched\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("inputs/day3_example.txt")).unwrap(),
+            ""
+        );
+        let answers = fs::read_to_string(root.join("answers.toml")).unwrap();
+        assert!(crate::answers::Answers::parse(&answers).is_ok());
+        assert!(answers.contains("[day3.example]"));
+
+        // nothing is overwritten
+        let err = project.new_day(3).unwrap_err();
+        assert!(err.to_string().contains("exists already"), "{err:#}");
+        assert_eq!(
+            fs::read_to_string(root.join("src/day3.rs")).unwrap(),
+            solver
+        );
+        let err = project.new_day(1).unwrap_err();
+        assert!(err.to_string().contains("answers for day 1"), "{err:#}");
+        assert!(!root.join("src/day1.rs").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,162 @@
+//! A local HTTP service solving puzzles for other tools.
+//!
+//! `POST /day/{n}/part/{p}` with the puzzle input as the body answers with JSON:
+//! `{"day": n, "part": p, "answer": "…", "time_ns": …}`, or `{"error": "…"}` with
+//! status 400 if the input is invalid, 422 if the solver fails, 500 if it panics, and
+//! 404 for unknown days. The parameters (see [`crate::params`]) are those of the real
+//! input, or of another input named with `?input=<name>`, e.g. `?input=example`.
+
+use std::{io::Read, net::Ipv4Addr, time::Instant};
+
+use anyhow::{anyhow, bail, Result};
+use json::{object, JsonValue};
+use log::info;
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::{params::Config, runner::catch, solver, Part};
+
+/// The largest input accepted, far above the size of any puzzle input.
+const MAX_BODY: u64 = 16 << 20;
+
+/// The HTTP status and the JSON body of a response.
+type Reply = (u16, JsonValue);
+
+fn error(status: u16, message: impl Into<String>) -> Reply {
+    (status, object! { error: message.into() })
+}
+
+pub struct Server {
+    http: tiny_http::Server,
+    config: Config,
+}
+
+impl Server {
+    /// Listens on the given port of localhost only (0 for any free port).
+    pub fn bind(port: u16) -> Result<Self> {
+        let http = tiny_http::Server::http((Ipv4Addr::LOCALHOST, port))
+            .map_err(|e| anyhow!("cannot listen on port {port}: {e}"))?;
+        Ok(Server {
+            http,
+            config: Config::load()?,
+        })
+    }
+
+    /// The port the server listens on.
+    pub fn port(&self) -> u16 {
+        self.http
+            .server_addr()
+            .to_ip()
+            .map_or(0, |addr| addr.port())
+    }
+
+    /// Answers requests, one at a time, until the process ends.
+    pub fn run(&self) {
+        for request in self.http.incoming_requests() {
+            self.respond(request);
+        }
+    }
+
+    fn respond(&self, mut request: Request) {
+        let mut body = Vec::new();
+        let (status, json) = match request
+            .as_reader()
+            .take(MAX_BODY + 1)
+            .read_to_end(&mut body)
+        {
+            Ok(_) if body.len() as u64 > MAX_BODY => error(413, "the input is too large"),
+            Ok(_) => self.handle(request.method(), request.url(), body),
+            Err(e) => error(400, format!("cannot read the input: {e}")),
+        };
+        info!("{} {}: {status}", request.method(), request.url());
+        let response = Response::from_string(json.dump())
+            .with_status_code(status)
+            .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+        if let Err(e) = request.respond(response) {
+            info!("cannot send the response: {e}");
+        }
+    }
+
+    fn handle(&self, method: &Method, url: &str, body: Vec<u8>) -> Reply {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let Some((day, part)) = route(path) else {
+            return error(
+                404,
+                format!("no such resource {path}, try POST /day/{{n}}/part/{{p}}"),
+            );
+        };
+        if *method != Method::Post {
+            return error(405, format!("{path} only accepts POST"));
+        }
+        let Some(solver) = solver(day) else {
+            return error(404, format!("no solver for day {day}"));
+        };
+        let input = match query_input(query) {
+            Ok(input) => input,
+            Err(e) => return error(400, format!("{e:#}")),
+        };
+        let Ok(text) = String::from_utf8(body) else {
+            return error(400, "the input is not valid UTF-8");
+        };
+        let params = self.config.params(day, input);
+
+        let start = Instant::now();
+        let mut parsed = false;
+        let answer = catch(|| {
+            let input = solver.parse_input(&text)?;
+            parsed = true;
+            solver.solve_part(input.as_ref(), part, &params)
+        });
+        let time = start.elapsed();
+        match answer {
+            Ok(answer) => (
+                200,
+                object! {
+                    day: day,
+                    part: part.number(),
+                    answer: answer,
+                    time_ns: time.as_nanos() as u64,
+                },
+            ),
+            Err(e) if e.starts_with("panicked") => error(500, e),
+            Err(e) if !parsed => error(400, e),
+            Err(e) => error(422, e),
+        }
+    }
+}
+
+/// The day and part of a path `/day/{n}/part/{p}`.
+fn route(path: &str) -> Option<(u8, Part)> {
+    match path.trim_matches('/').split('/').collect::<Vec<_>>()[..] {
+        ["day", day, "part", part] => Some((day.parse().ok()?, part.parse().ok()?)),
+        _ => None,
+    }
+}
+
+/// The input named in the query, whose parameters are used.
+fn query_input(query: &str) -> Result<&str> {
+    let mut input = "real";
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        match pair.split_once('=') {
+            Some(("input", name)) if !name.is_empty() => input = name,
+            _ => bail!("unknown query {pair}, expected ?input=<name>"),
+        }
+    }
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes() {
+        assert_eq!(route("/day/1/part/2"), Some((1, Part::Two)));
+        assert_eq!(route("/day/15/part/1/"), Some((15, Part::One)));
+        assert_eq!(route("/day/1/part/3"), None);
+        assert_eq!(route("/day/x/part/1"), None);
+        assert_eq!(route("/day/1"), None);
+        assert_eq!(query_input("").unwrap(), "real");
+        assert_eq!(query_input("input=example").unwrap(), "example");
+        assert!(query_input("seed=1").is_err());
+    }
+}
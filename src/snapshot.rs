@@ -0,0 +1,121 @@
+//! Snapshot tests: renderings of puzzle states are compared with the expected text
+//! checked in under `snapshots/`. Set [`UPDATE_VAR`] to rewrite the snapshots with
+//! the current renderings instead, then review the changes with `git diff`.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+/// The environment variable which, set to anything but `0`, makes the snapshot checks
+/// write the renderings instead of comparing them.
+pub const UPDATE_VAR: &str = "AOC_UPDATE_SNAPSHOTS";
+
+const SNAPSHOT_DIR: &str = "snapshots";
+
+/// A directory of snapshots, one `<name>.txt` file each.
+pub struct Snapshots {
+    dir: PathBuf,
+    update: bool,
+}
+
+impl Snapshots {
+    /// The checked-in snapshots in the crate root, updated if [`UPDATE_VAR`] is set.
+    pub fn from_env() -> Self {
+        let update = env::var(UPDATE_VAR).is_ok_and(|v| !v.is_empty() && v != "0");
+        Snapshots::new(Path::new(env!("CARGO_MANIFEST_DIR")).join(SNAPSHOT_DIR)).update(update)
+    }
+
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Snapshots {
+            dir: dir.into(),
+            update: false,
+        }
+    }
+
+    /// Write the renderings instead of comparing them.
+    pub fn update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.txt"))
+    }
+
+    /// Compares the rendering with the snapshot of the given name (or writes it).
+    pub fn check(&self, name: &str, actual: &str) -> Result<()> {
+        let path = self.path(name);
+        if self.update {
+            fs::create_dir_all(&self.dir)
+                .with_context(|| format!("cannot create {}", self.dir.display()))?;
+            return fs::write(&path, actual)
+                .with_context(|| format!("cannot write {}", path.display()));
+        }
+        let expected = match fs::read_to_string(&path) {
+            Ok(expected) => expected,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                bail!(
+                    "no snapshot {} (set {UPDATE_VAR}=1 to write it)",
+                    path.display()
+                )
+            }
+            Err(e) => return Err(e).with_context(|| format!("cannot read {}", path.display())),
+        };
+        if expected == actual {
+            return Ok(());
+        }
+        let mut expected_lines = expected.split('\n');
+        let mut actual_lines = actual.split('\n');
+        for line in 1.. {
+            match (expected_lines.next(), actual_lines.next()) {
+                (e, a) if e == a => continue,
+                (e, a) => bail!(
+                    "snapshot {name} differs in line {line} (set {UPDATE_VAR}=1 to accept the change)\n\
+                     expected: {}\n  actual: {}",
+                    e.map_or("<end>".into(), |l| format!("{l:?}")),
+                    a.map_or("<end>".into(), |l| format!("{l:?}")),
+                ),
+            }
+        }
+        unreachable!("the renderings differ in some line")
+    }
+}
+
+/// Asserts that the rendering matches the checked-in snapshot of the given name.
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    if let Err(e) = Snapshots::from_env().check(name, actual) {
+        panic!("{e:#}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_and_update() {
+        let dir = env::temp_dir().join(format!("aoc-2022-snapshots-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let snapshots = Snapshots::new(&dir);
+
+        let err = snapshots.check("grid", "#.\n.#\n").unwrap_err();
+        assert!(err.to_string().starts_with("no snapshot"), "{err}");
+
+        Snapshots::new(&dir)
+            .update(true)
+            .check("grid", "#.\n.#\n")
+            .unwrap();
+        snapshots.check("grid", "#.\n.#\n").unwrap();
+
+        let err = snapshots.check("grid", "#.\n##\n").unwrap_err().to_string();
+        assert!(err.contains("differs in line 2"), "{err}");
+        assert!(err.ends_with("expected: \".#\"\n  actual: \"##\""), "{err}");
+        let err = snapshots.check("grid", "#.\n").unwrap_err().to_string();
+        assert!(err.ends_with("expected: \".#\"\n  actual: \"\""), "{err}");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+use std::{any::Any, fmt, str::FromStr};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::{Params, ParseError};
+
+/// One of the two parts of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            p => bail!("invalid part {p}, expected 1 or 2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let num: u8 = s.parse().map_err(|_| anyhow!("expected 1 or 2"))?;
+        num.try_into()
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A day's puzzle: parses the input once, then solves both parts on the parsed input,
+/// with the [`Params`] of the input.
+pub trait Solution {
+    /// The day of the puzzle (1-25).
+    const DAY: u8;
+
+    /// The parsed puzzle input, shared by both parts.
+    type Input: 'static;
+
+    /// Parse the input, reporting where it is malformed.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input, params: &Params) -> Result<impl fmt::Display>;
+
+    fn part2(input: &Self::Input, params: &Params) -> Result<impl fmt::Display>;
+}
+
+/// Object-safe view of a [`Solution`], so that all days can be collected and run by number.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    /// Run the parsing stage only.
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any>>;
+
+    /// Solve one part, given the result of [`Solver::parse_input`].
+    fn solve_part(&self, parsed: &dyn Any, part: Part, params: &Params) -> Result<String>;
+
+    /// Parse the input and solve one part.
+    fn run(&self, input: &str, part: Part, params: &Params) -> Result<String> {
+        let parsed = self.parse_input(input)?;
+        self.solve_part(parsed.as_ref(), part, params)
+    }
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(<S as Solution>::parse(input)?))
+    }
+
+    fn solve_part(&self, parsed: &dyn Any, part: Part, params: &Params) -> Result<String> {
+        let parsed = parsed
+            .downcast_ref::<S::Input>()
+            .context("parsed input belongs to a different day")?;
+        match part {
+            Part::One => Ok(S::part1(parsed, params)?.to_string()),
+            Part::Two => Ok(S::part2(parsed, params)?.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_part() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("3".parse::<Part>().is_err());
+        assert!("one".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn parsed_input_of_other_day() {
+        let day1 = crate::solver(1).unwrap();
+        let day2 = crate::solver(2).unwrap();
+        let parsed = day1.parse_input("1000\n2000\n").unwrap();
+        let params = Params::new(1);
+        let answer = day1.solve_part(parsed.as_ref(), Part::One, &params);
+        assert_eq!(answer.unwrap(), "3000");
+        assert!(day2
+            .solve_part(parsed.as_ref(), Part::One, &params)
+            .is_err());
+    }
+}
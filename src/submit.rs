@@ -0,0 +1,235 @@
+//! Submitting answers: a local log of the guesses made, so that known-wrong answers
+//! are never sent again, and the website's cooldown between answers is respected.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Client, Hint, Verdict},
+    Part,
+};
+
+const GUESSES_FILE: &str = "guesses.toml";
+
+/// How often the website may ask us to wait before an answer is accepted.
+const MAX_ATTEMPTS: usize = 3;
+
+/// What the website said about a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Outcome {
+    fn wrong(hint: Option<Hint>) -> Self {
+        match hint {
+            Some(Hint::TooHigh) => Outcome::TooHigh,
+            Some(Hint::TooLow) => Outcome::TooLow,
+            None => Outcome::Wrong,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// The guess log, stored as `guesses.toml` next to the inputs.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guesses {
+    /// No answers may be submitted before this time (seconds since the epoch).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wait_until: Option<u64>,
+    #[serde(default, rename = "guess")]
+    guesses: Vec<Guess>,
+}
+
+impl Guesses {
+    /// The path of the guess log in the input directory.
+    pub fn path() -> PathBuf {
+        crate::input_dir().join(GUESSES_FILE)
+    }
+
+    /// Load the guess log; a missing file is an empty log.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                toml::from_str(&text).with_context(|| format!("invalid {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Guesses::default()),
+            Err(e) => Err(e).with_context(|| format!("cannot read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("cannot write {}", path.display()))
+    }
+
+    /// All guesses for one part of one day, oldest first.
+    pub fn of(&self, day: u8, part: Part) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |g| g.day == day && g.part == part.number())
+    }
+
+    /// Fails if `answer` is known to be wrong, from an earlier guess or its hint.
+    fn check(&self, day: u8, part: Part, answer: &str) -> Result<()> {
+        let number = answer.parse::<i64>().ok();
+        for guess in self.of(day, part) {
+            if guess.answer == answer && guess.outcome != Outcome::Correct {
+                bail!("{answer} was submitted before and is wrong");
+            }
+            let bound = guess.answer.parse::<i64>().ok();
+            match (guess.outcome, number, bound) {
+                (Outcome::TooHigh, Some(n), Some(b)) if n >= b => {
+                    bail!("{answer} is too high: {b} was too high already")
+                }
+                (Outcome::TooLow, Some(n), Some(b)) if n <= b => {
+                    bail!("{answer} is too low: {b} was too low already")
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, day: u8, part: Part, answer: &str, outcome: Outcome) {
+        self.guesses.push(Guess {
+            day,
+            part: part.number(),
+            answer: answer.to_owned(),
+            outcome,
+        });
+    }
+}
+
+/// Seconds since the epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Submit an answer unless the log shows it is wrong, waiting out cooldowns with
+/// `sleep`. Returns the outcome; an answer that was accepted before is not sent again.
+pub fn submit(
+    client: &Client,
+    guesses: &mut Guesses,
+    day: u8,
+    part: Part,
+    answer: &str,
+    mut sleep: impl FnMut(Duration),
+) -> Result<Outcome> {
+    if let Some(solved) = guesses
+        .of(day, part)
+        .find(|g| g.outcome == Outcome::Correct)
+    {
+        if solved.answer == answer {
+            return Ok(Outcome::Correct);
+        }
+        bail!("already solved with {}, not {answer}", solved.answer);
+    }
+    guesses.check(day, part, answer)?;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let start = now();
+        if let Some(until) = guesses.wait_until.filter(|&t| t > start) {
+            sleep(Duration::from_secs(until - start));
+        }
+
+        let outcome = match client.submit(day, part, answer)? {
+            Verdict::Correct => Outcome::Correct,
+            Verdict::Wrong { hint, wait } => {
+                guesses.wait_until = Some(now() + wait.as_secs());
+                Outcome::wrong(hint)
+            }
+            Verdict::TooRecent(wait) => {
+                guesses.wait_until = Some(now() + wait.as_secs());
+                continue;
+            }
+            Verdict::WrongLevel => {
+                bail!("day {day} part {part} cannot be answered: already solved, or part 1 is missing")
+            }
+        };
+        guesses.record(day, part, answer, outcome);
+        return Ok(outcome);
+    }
+    bail!("still not allowed to answer after waiting {MAX_ATTEMPTS} times")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use crate::client::stub;
+
+    use super::*;
+
+    const WRONG: &str = "<p>That's not the right answer; your answer is too low. Please wait one minute before trying again.</p>";
+    const RIGHT: &str = "<p>That's the right answer! You are one gold star closer.</p>";
+    const TOO_RECENT: &str = "<p>You gave an answer too recently. You have 7s left to wait.</p>";
+
+    #[test]
+    fn submit_and_record() {
+        let (url, server) = stub::serve(vec![(200, WRONG), (200, TOO_RECENT), (200, RIGHT)]);
+        let client = Client::new(&url, "token");
+        let mut guesses = Guesses::default();
+        let waits = RefCell::new(Vec::new());
+        let sleep = |d: Duration| waits.borrow_mut().push(d.as_secs());
+
+        let outcome = submit(&client, &mut guesses, 1, Part::Two, "100", &sleep).unwrap();
+        assert_eq!(outcome, Outcome::TooLow);
+
+        // known wrong answers are refused without asking the server
+        assert!(submit(&client, &mut guesses, 1, Part::Two, "100", &sleep).is_err());
+        assert!(submit(&client, &mut guesses, 1, Part::Two, "99", &sleep).is_err());
+
+        // waits for the cooldown after the wrong answer, and once more when told to
+        let outcome = submit(&client, &mut guesses, 1, Part::Two, "120", &sleep).unwrap();
+        assert_eq!(outcome, Outcome::Correct);
+        let waits = waits.take();
+        assert_eq!(waits.len(), 2);
+        assert!((59..=60).contains(&waits[0]));
+        assert!((6..=7).contains(&waits[1]));
+
+        // solved: nothing is sent anymore
+        let outcome = submit(&client, &mut guesses, 1, Part::Two, "120", &sleep).unwrap();
+        assert_eq!(outcome, Outcome::Correct);
+        assert!(submit(&client, &mut guesses, 1, Part::Two, "121", &sleep).is_err());
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].line, "POST /2022/day/1/answer HTTP/1.1");
+        assert_eq!(requests[0].body, "level=2&answer=100");
+        assert_eq!(guesses.of(1, Part::Two).count(), 2);
+    }
+
+    #[test]
+    fn log_roundtrip() {
+        let mut guesses = Guesses {
+            wait_until: Some(1_700_000_000),
+            ..Guesses::default()
+        };
+        guesses.record(5, Part::One, "CMZ", Outcome::Wrong);
+        guesses.record(5, Part::One, "ZWHVFWQWW", Outcome::Correct);
+
+        let text = toml::to_string(&guesses).unwrap();
+        assert!(text.contains("outcome = \"correct\""));
+        assert_eq!(toml::from_str::<Guesses>(&text).unwrap(), guesses);
+    }
+}
@@ -0,0 +1,21 @@
+//! One test per entry of the answers registry (`answers.toml`).
+
+use aoc_2022::answers::Answers;
+use libtest_mimic::{Arguments, Failed, Trial};
+
+fn main() {
+    let args = Arguments::from_args();
+    let answers = Answers::load().expect("the answers file should be valid");
+
+    let trials = answers
+        .iter()
+        .cloned()
+        .map(|entry| {
+            Trial::test(entry.name(), move || {
+                entry.check().map_err(|e| Failed::from(format!("{e:#}")))
+            })
+        })
+        .collect();
+
+    libtest_mimic::run(&args, trials).exit();
+}
@@ -0,0 +1,63 @@
+//! The days' domain models, used from outside the crate.
+
+use aoc_2022::{
+    cancel::Cancel,
+    day11::{self, Day11},
+    day13::{self, Value},
+    day16::Day16,
+    day4::Work,
+    day9,
+    geom::Direction,
+    Solution,
+};
+
+#[test]
+fn camp_cleanup() {
+    let outer = Work::new(2, 8);
+    let inner = Work::new(3, 7);
+    assert!(outer.contains(&inner));
+    assert!(!inner.contains(&outer));
+    assert!(!Work::new(1, 2).overlaps(&Work::new(3, 4)));
+}
+
+#[test]
+fn rope() {
+    let mut rope = day9::Grid::new(2);
+    rope.do_move(Direction::Right, 3);
+    assert_eq!(
+        rope.rope(),
+        [day9::Point::new(3, 0), day9::Point::new(2, 0)]
+    );
+    assert_eq!(rope.visited().len(), 3);
+}
+
+#[test]
+fn monkeys() {
+    let monkeys = Day11::parse(&aoc_2022::example(11).unwrap()).unwrap();
+    assert_eq!(monkeys[0].items().collect::<Vec<_>>(), [79, 98]);
+    assert_eq!(monkeys[0].divisor(), 23);
+    let counts = day11::inspections(&monkeys, 20, true, &Cancel::default()).unwrap();
+    assert_eq!(counts, [101, 95, 7, 105]);
+    assert_eq!(day11::monkey_business(counts).unwrap(), 10605);
+}
+
+#[test]
+fn packets() {
+    let pairs: Vec<Vec<Value>> = [["[1,[2]]", "[[1],3]"], ["[[]]", "[]"]]
+        .iter()
+        .map(|pair| pair.iter().map(|p| p.parse().unwrap()).collect())
+        .collect();
+    assert_eq!(day13::right_order_sum(&pairs), 1);
+    let dividers: Vec<Value> = ["[[2]]", "[[6]]"].map(|d| d.parse().unwrap()).into();
+    assert_eq!(day13::divider_positions(&pairs, &dividers), [5, 6]);
+}
+
+#[test]
+fn valves() {
+    let network = Day16::parse(&aoc_2022::example(16).unwrap()).unwrap();
+    let aa = network.valves().next().unwrap();
+    assert_eq!(aa.label().to_string(), "AA");
+    assert_eq!(aa.rate(), 0);
+    let neighbours: Vec<String> = aa.neighbours().iter().map(|n| n.to_string()).collect();
+    assert_eq!(neighbours, ["DD", "II", "BB"]);
+}
@@ -0,0 +1,58 @@
+//! The HTTP service, end to end on a local port.
+
+use std::thread;
+
+use aoc_2022::serve::Server;
+use ureq::Agent;
+
+/// Posts the input and returns the status and the JSON body.
+fn post(agent: &Agent, url: &str, input: &str) -> (u16, json::JsonValue) {
+    let mut response = agent.post(url).send(input).unwrap();
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string().unwrap();
+    (status, json::parse(&body).unwrap())
+}
+
+#[test]
+fn solve_over_http() {
+    let server = Server::bind(0).unwrap();
+    let base = format!("http://127.0.0.1:{}", server.port());
+    thread::spawn(move || server.run());
+    let agent: Agent = Agent::config_builder()
+        .http_status_as_error(false)
+        .build()
+        .into();
+
+    let example = aoc_2022::example(1).unwrap();
+    let (status, js) = post(&agent, &format!("{base}/day/1/part/2"), &example);
+    assert_eq!(status, 200, "{js}");
+    assert_eq!(js["answer"], "45000");
+    assert_eq!((js["day"].as_u8(), js["part"].as_u8()), (Some(1), Some(2)));
+    assert!(js["time_ns"].as_u64().is_some());
+
+    // the parameters of the named input
+    let example = aoc_2022::example(15).unwrap();
+    let (status, js) = post(
+        &agent,
+        &format!("{base}/day/15/part/1?input=example"),
+        &example,
+    );
+    assert_eq!((status, js["answer"].as_str()), (200, Some("26")));
+
+    let (status, js) = post(&agent, &format!("{base}/day/1/part/1"), "1000\nabc\n");
+    assert_eq!(status, 400);
+    assert!(js["error"].as_str().unwrap().contains("line 2"), "{js}");
+    let (status, js) = post(&agent, &format!("{base}/day/1/part/2"), "1000\n");
+    assert_eq!(status, 422, "{js}");
+    let (status, _) = post(&agent, &format!("{base}/day/25/part/1"), "1\n");
+    assert_eq!(status, 404);
+    let (status, _) = post(&agent, &format!("{base}/days"), "");
+    assert_eq!(status, 404);
+    let mut response = agent.get(format!("{base}/day/1/part/1")).call().unwrap();
+    assert_eq!(response.status().as_u16(), 405);
+    assert!(response
+        .body_mut()
+        .read_to_string()
+        .unwrap()
+        .contains("POST"));
+}